@@ -1,16 +1,16 @@
 use async_std::task;
 use ggez::conf::{WindowMode, WindowSetup};
 use ggez::event;
-use ggez::event::{EventHandler, MouseButton};
-use ggez::graphics::{self, Color};
-use ggez::{Context, ContextBuilder, GameResult};
+use ggez::input::keyboard::KeyCode;
+use ggez::ContextBuilder;
 use std::sync::{Arc, Mutex};
 use yorool_gui2::button::ButtonMode::{Checkbox, PressButton, Radio};
 use yorool_gui2::default_skin::{ButtonBuilder, ButtonId, RibbonBuilder};
+use yorool_gui2::guiroot::GuiRootBuilder;
 use yorool_gui2::radiogroup::{RadioGroupBuilder, RadioGroupId};
 use yorool_gui2::ribbon::RibbonId;
 use yorool_gui2::ribbon::RibbonOrientation::{Horizontal, Vertical};
-use yorool_gui2::{EventHandlerProxy, Widget, WidgetGroup};
+use yorool_gui2::{Widget, WidgetGroup};
 
 struct DemoPanelState {
     button_ids: Vec<ButtonId>,
@@ -136,50 +136,14 @@ impl WidgetGroup for DemoPanel {
     }
 }
 
-struct GuiDemoState {
-    panel: DemoPanel,
-}
-
-impl GuiDemoState {
-    fn new() -> Self {
-        Self {
-            panel: DemoPanel::new(),
-        }
-    }
-}
-
-impl EventHandler for GuiDemoState {
-    fn update(&mut self, ctx: &mut Context) -> GameResult {
-        self.panel.update(ctx)
-    }
-
-    fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        graphics::clear(ctx, Color::new(0., 0., 0., 0.));
-        self.panel.draw(ctx)?;
-        graphics::present(ctx)
-    }
-
-    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
-        self.panel.mouse_button_down_event(ctx, button, x, y)
-    }
-
-    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
-        self.panel.mouse_button_up_event(ctx, button, x, y)
-    }
-
-    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) {
-        let new_rect = graphics::Rect::new(0., 0., width, height);
-        self.panel.set_rect(new_rect.clone());
-        graphics::set_screen_coordinates(ctx, new_rect).unwrap();
-    }
-}
-
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cb = ContextBuilder::new("yorool_gui_demo", "milyin")
         .window_setup(WindowSetup::default().title("Yorool GUI demo"))
         .window_mode(WindowMode::default().resizable(true));
     let (ctx, event_loop) = &mut cb.build()?;
-    let state = &mut GuiDemoState::new();
+    let state = &mut GuiRootBuilder::new(DemoPanel::new())
+        .set_inspector_key(KeyCode::F12)
+        .build();
     event::run(ctx, event_loop, state)?;
     Ok(())
 }
@@ -1,30 +1,27 @@
 use crate::{add_to_indexmap, EventHandlerProxy, Widget};
 use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
 use ggez::event::MouseButton;
-use ggez::graphics::Rect;
+use ggez::graphics::{self, Rect};
 use ggez::{Context, GameResult};
 use indexmap::map::IndexMap;
 use std::fmt::{Debug, Formatter};
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub enum ButtonMode {
+    #[default]
     PressButton,
     Checkbox(bool),
     Radio(bool),
     //Checkbox3State(), - or even N-state, may be added later
 }
 
-impl Default for ButtonMode {
-    fn default() -> Self {
-        ButtonMode::PressButton
-    }
-}
-
 #[derive(Clone, Default, Debug)]
 pub struct ButtonState {
     pub mode: ButtonMode,
     pub touched: bool,
     pub label: String,
+    /// Drawn next to the label or alone if the label is empty
+    pub icon: Option<graphics::Image>,
     pub rect: Rect,
 }
 
@@ -42,12 +39,17 @@ impl From<ButtonId> for SrvId {
     }
 }
 
+type ClickHandler = Box<dyn Fn(&mut dyn Widget) + Send + Sync>;
+
 enum ButtonOp {
     GetMode,
     SetMode(ButtonMode),
     GetLabel,
     SetLabel(String),
-    OnClick(Box<dyn Fn(&mut dyn Widget) + Send + Sync>),
+    SetIcon(Option<graphics::Image>),
+    GetTooltip,
+    SetTooltip(Option<String>),
+    OnClick(ClickHandler),
     RemoveOnClick(usize),
 }
 
@@ -58,6 +60,9 @@ impl Debug for ButtonOp {
             ButtonOp::SetMode(mode) => write!(f, "SetMode({:?}", mode),
             ButtonOp::GetLabel => write!(f, "GetLabel"),
             ButtonOp::SetLabel(label) => write!(f, "SetLabel({:?})", label),
+            ButtonOp::SetIcon(icon) => write!(f, "SetIcon({:?})", icon),
+            ButtonOp::GetTooltip => write!(f, "GetTooltip"),
+            ButtonOp::SetTooltip(tooltip) => write!(f, "SetTooltip({:?})", tooltip),
             ButtonOp::OnClick(_) => write!(f, "OnClick"),
             ButtonOp::RemoveOnClick(handler_id) => write!(f, "RemoveOnClick({:?})", handler_id),
         }
@@ -79,6 +84,17 @@ impl ButtonId {
             .await
             .unwrap()
     }
+    pub async fn set_icon(self, icon: Option<graphics::Image>) {
+        send_request(self.0, ButtonOp::SetIcon(icon)).await.unwrap()
+    }
+    pub async fn get_tooltip(self) -> Option<String> {
+        send_request(self.0, ButtonOp::GetTooltip).await.unwrap()
+    }
+    pub async fn set_tooltip(self, tooltip: Option<String>) {
+        send_request(self.0, ButtonOp::SetTooltip(tooltip))
+            .await
+            .unwrap()
+    }
     pub async fn on_click<F: Fn(&mut dyn Widget) + Send + Sync + 'static>(self, f: F) -> usize {
         send_request(self.0, ButtonOp::OnClick(Box::new(f)))
             .await
@@ -95,7 +111,8 @@ pub struct Button<S: ButtonSkin> {
     state: ButtonState,
     skin: S,
     reg: ServiceRegistration,
-    on_click_handlers: IndexMap<usize, Box<dyn Fn(&mut dyn Widget) + Send + Sync>>,
+    tooltip: Option<String>,
+    on_click_handlers: IndexMap<usize, ClickHandler>,
 }
 
 impl<S: ButtonSkin> Button<S> {
@@ -104,6 +121,7 @@ impl<S: ButtonSkin> Button<S> {
             state: ButtonState::default(),
             skin: S::default(),
             reg: register_service(),
+            tooltip: None,
             on_click_handlers: IndexMap::new(),
         }
     }
@@ -122,7 +140,19 @@ impl<S: ButtonSkin> Button<S> {
     pub fn get_label(&self) -> &str {
         self.state.label.as_str()
     }
-    pub fn on_click_box(&mut self, handler: Box<dyn Fn(&mut dyn Widget) + Send + Sync>) -> usize {
+    pub fn set_icon(&mut self, icon: Option<graphics::Image>) {
+        self.state.icon = icon
+    }
+    pub fn get_icon(&self) -> Option<&graphics::Image> {
+        self.state.icon.as_ref()
+    }
+    pub fn set_tooltip(&mut self, tooltip: Option<String>) {
+        self.tooltip = tooltip
+    }
+    pub fn get_tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+    pub fn on_click_box(&mut self, handler: ClickHandler) -> usize {
         add_to_indexmap(&mut self.on_click_handlers, handler)
     }
     pub fn on_click<F: Fn(&mut dyn Widget) + Send + Sync + 'static>(&mut self, f: F) -> usize {
@@ -133,6 +163,12 @@ impl<S: ButtonSkin> Button<S> {
     }
 }
 
+impl<S: ButtonSkin> Default for Button<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<S: ButtonSkin + 'static> Widget for Button<S> {
     fn srv_id(&self) -> SrvId {
         self.reg.id()
@@ -143,6 +179,15 @@ impl<S: ButtonSkin + 'static> Widget for Button<S> {
     fn get_rect(&self) -> Option<Rect> {
         Some(self.state.rect)
     }
+    fn tooltip(&self) -> Option<&str> {
+        self.get_tooltip()
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("mode", format!("{:?}", self.state.mode)),
+            ("label", self.state.label.clone()),
+        ]
+    }
 }
 
 impl<S: ButtonSkin + 'static> EventHandlerProxy for Button<S> {
@@ -158,6 +203,15 @@ impl<S: ButtonSkin + 'static> EventHandlerProxy for Button<S> {
                 self.set_label(label);
                 Some(Box::new(()))
             }
+            ButtonOp::SetIcon(icon) => {
+                self.set_icon(icon);
+                Some(Box::new(()))
+            }
+            ButtonOp::GetTooltip => Some(Box::new(self.tooltip.clone())),
+            ButtonOp::SetTooltip(tooltip) => {
+                self.set_tooltip(tooltip);
+                Some(Box::new(()))
+            }
             ButtonOp::OnClick(handler) => {
                 let handler_id = self.on_click_box(handler);
                 Some(Box::new(handler_id))
@@ -172,6 +226,8 @@ impl<S: ButtonSkin + 'static> EventHandlerProxy for Button<S> {
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        // state could be changed by events since last update
+        self.skin.set_state(&self.state);
         self.skin.draw(ctx)
     }
 
@@ -181,38 +237,42 @@ impl<S: ButtonSkin + 'static> EventHandlerProxy for Button<S> {
         _button: MouseButton,
         x: f32,
         y: f32,
-    ) {
-        if _button == MouseButton::Left {
-            if self.skin.is_hot_area(x, y) {
-                self.state.touched = true;
-            }
+    ) -> bool {
+        if _button == MouseButton::Left && self.skin.is_hot_area(x, y) {
+            self.state.touched = true;
+            return true;
         }
+        false
     }
 
-    fn mouse_button_up_event(&mut self, _ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
-        if button == MouseButton::Left {
-            if self.state.touched {
-                self.state.touched = false;
-                if self.skin.is_hot_area(x, y) {
-                    match &self.state.mode {
-                        ButtonMode::PressButton => {}
-                        ButtonMode::Checkbox(check) => {
-                            self.state.mode = ButtonMode::Checkbox(!*check)
-                        }
-                        ButtonMode::Radio(check) => {
-                            if !check {
-                                self.state.mode = ButtonMode::Radio(true)
-                            }
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button == MouseButton::Left && self.state.touched {
+            self.state.touched = false;
+            if self.skin.is_hot_area(x, y) {
+                match &self.state.mode {
+                    ButtonMode::PressButton => {}
+                    ButtonMode::Checkbox(check) => self.state.mode = ButtonMode::Checkbox(!*check),
+                    ButtonMode::Radio(check) => {
+                        if !check {
+                            self.state.mode = ButtonMode::Radio(true)
                         }
                     }
-                    let handlers = std::mem::replace(&mut self.on_click_handlers, IndexMap::new());
-                    for (_, handler) in &handlers {
-                        handler(self);
-                    }
-                    self.on_click_handlers = handlers;
                 }
+                let handlers = std::mem::replace(&mut self.on_click_handlers, IndexMap::new());
+                for (_, handler) in &handlers {
+                    handler(self);
+                }
+                self.on_click_handlers = handlers;
             }
+            return true;
         }
+        false
     }
 }
 
@@ -220,6 +280,12 @@ pub struct ButtonBuilder<S: ButtonSkin> {
     button: Button<S>,
 }
 
+impl<S: ButtonSkin> Default for ButtonBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<S: ButtonSkin> ButtonBuilder<S> {
     pub fn new() -> Self {
         Self {
@@ -234,6 +300,14 @@ impl<S: ButtonSkin> ButtonBuilder<S> {
         self.button.set_label(label.into());
         self
     }
+    pub fn set_icon(mut self, icon: graphics::Image) -> Self {
+        self.button.set_icon(Some(icon));
+        self
+    }
+    pub fn set_tooltip<T: Into<String>>(mut self, tooltip: T) -> Self {
+        self.button.set_tooltip(Some(tooltip.into()));
+        self
+    }
     pub fn on_click<F: Fn(&mut dyn Widget) + Send + Sync + 'static>(mut self, f: F) -> Self {
         self.button.on_click(f);
         self
@@ -0,0 +1,323 @@
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::mouse;
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// Where and why the canvas is drawn
+#[derive(Copy, Clone, Debug)]
+pub struct CanvasFrame {
+    pub rect: Rect,
+    /// Set on the first draw and after the data or the rect was changed, an
+    /// event was consumed or redraw was requested. Draw closure may keep
+    /// meshes built from the data in the data itself and rebuild them only
+    /// when the frame is dirty.
+    pub dirty: bool,
+}
+
+/// Mouse event with coordinates relative to the canvas' top left corner
+#[derive(Copy, Clone, Debug)]
+pub enum CanvasEvent {
+    MouseButtonDown {
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    },
+    MouseButtonUp {
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    },
+    MouseMotion {
+        x: f32,
+        y: f32,
+        dx: f32,
+        dy: f32,
+    },
+    MouseWheel {
+        x: f32,
+        y: f32,
+        wheel_x: f32,
+        wheel_y: f32,
+    },
+}
+
+pub struct CanvasId<T>(SrvId, PhantomData<fn(T)>);
+
+impl<T> Copy for CanvasId<T> {}
+
+impl<T> Clone for CanvasId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Debug for CanvasId<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CanvasId({:?})", self.0)
+    }
+}
+
+impl<T> PartialEq for CanvasId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> From<CanvasId<T>> for SrvId {
+    fn from(v: CanvasId<T>) -> SrvId {
+        v.0
+    }
+}
+
+type WithDataFn<T> = Box<dyn FnOnce(&mut T) -> Box<dyn Any + Send> + Send>;
+
+enum CanvasOp<T> {
+    SetData(T),
+    WithData(WithDataFn<T>),
+    Redraw,
+}
+
+impl<T> Debug for CanvasOp<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CanvasOp::SetData(_) => write!(f, "SetData"),
+            CanvasOp::WithData(_) => write!(f, "WithData"),
+            CanvasOp::Redraw => write!(f, "Redraw"),
+        }
+    }
+}
+
+impl<T: Send + 'static> CanvasId<T> {
+    pub async fn set_data(self, data: T) {
+        send_request(self.0, CanvasOp::SetData(data)).await.unwrap()
+    }
+    /// Reads or changes the canvas data, the canvas is redrawn after it
+    pub async fn with_data<R, F>(self, f: F) -> R
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut T) -> R + Send + 'static,
+    {
+        let f = move |data: &mut T| -> Box<dyn Any + Send> { Box::new(f(data)) };
+        send_request(self.0, CanvasOp::<T>::WithData(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn redraw(self) {
+        send_request(self.0, CanvasOp::<T>::Redraw).await.unwrap()
+    }
+}
+
+type DrawFn<T> = Box<dyn Fn(&mut Context, &mut T, CanvasFrame) -> GameResult + Send + Sync>;
+type EventFn<T> = Box<dyn Fn(&mut T, CanvasEvent, Rect) -> bool + Send + Sync>;
+
+/// Widget painted by the user closure over the user data. Mouse events are
+/// passed to the event closure in local coordinates; the closure returns
+/// `true` when the event is consumed.
+pub struct CanvasWidget<T: Send + 'static> {
+    rect: Rect,
+    reg: ServiceRegistration,
+    data: T,
+    dirty: bool,
+    /// Mouse button was pressed inside, so motion and release outside are
+    /// passed to the canvas too
+    captured: bool,
+    on_draw: Option<DrawFn<T>>,
+    on_event: Option<EventFn<T>>,
+}
+
+impl<T: Send + 'static> CanvasWidget<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            rect: Rect::default(),
+            reg: register_service(),
+            data,
+            dirty: true,
+            captured: false,
+            on_draw: None,
+            on_event: None,
+        }
+    }
+    pub fn id(&self) -> CanvasId<T> {
+        CanvasId(self.reg.id(), PhantomData)
+    }
+    pub fn set_data(&mut self, data: T) {
+        self.data = data;
+        self.dirty = true;
+    }
+    pub fn get_data(&self) -> &T {
+        &self.data
+    }
+    pub fn get_data_mut(&mut self) -> &mut T {
+        self.dirty = true;
+        &mut self.data
+    }
+    pub fn redraw(&mut self) {
+        self.dirty = true
+    }
+    pub fn on_draw<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Context, &mut T, CanvasFrame) -> GameResult + Send + Sync + 'static,
+    {
+        self.on_draw = Some(Box::new(f))
+    }
+    pub fn on_event<F>(&mut self, f: F)
+    where
+        F: Fn(&mut T, CanvasEvent, Rect) -> bool + Send + Sync + 'static,
+    {
+        self.on_event = Some(Box::new(f))
+    }
+    fn contains(&self, x: f32, y: f32) -> bool {
+        self.rect.contains(Point2::new(x, y))
+    }
+    fn send_event(&mut self, event: CanvasEvent) -> bool {
+        let consumed = match &self.on_event {
+            Some(on_event) => on_event(&mut self.data, event, self.rect),
+            None => false,
+        };
+        self.dirty |= consumed;
+        consumed
+    }
+}
+
+impl<T: Send + 'static> Widget for CanvasWidget<T> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        if rect != self.rect {
+            self.rect = rect;
+            self.dirty = true;
+        }
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![("dirty", self.dirty.to_string())]
+    }
+}
+
+impl<T: Send + 'static> EventHandlerProxy for CanvasWidget<T> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            CanvasOp::SetData(data) => {
+                self.set_data(data);
+                Some(Box::new(()))
+            }
+            CanvasOp::WithData(f) => Some(f(self.get_data_mut())),
+            CanvasOp::Redraw => {
+                self.redraw();
+                Some(Box::new(()))
+            }
+        });
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let frame = CanvasFrame {
+            rect: self.rect,
+            dirty: self.dirty,
+        };
+        self.dirty = false;
+        match &self.on_draw {
+            Some(on_draw) => on_draw(ctx, &mut self.data, frame),
+            None => Ok(()),
+        }
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if !self.contains(x, y) {
+            return false;
+        }
+        self.captured = true;
+        self.send_event(CanvasEvent::MouseButtonDown {
+            button,
+            x: x - self.rect.x,
+            y: y - self.rect.y,
+        })
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if !self.captured && !self.contains(x, y) {
+            return false;
+        }
+        self.captured = false;
+        self.send_event(CanvasEvent::MouseButtonUp {
+            button,
+            x: x - self.rect.x,
+            y: y - self.rect.y,
+        })
+    }
+
+    fn mouse_motion_event(&mut self, _ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        if !self.captured && !self.contains(x, y) {
+            return false;
+        }
+        self.send_event(CanvasEvent::MouseMotion {
+            x: x - self.rect.x,
+            y: y - self.rect.y,
+            dx,
+            dy,
+        })
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        let pos = mouse::position(ctx);
+        if !self.contains(pos.x, pos.y) {
+            return false;
+        }
+        self.send_event(CanvasEvent::MouseWheel {
+            x: pos.x - self.rect.x,
+            y: pos.y - self.rect.y,
+            wheel_x: x,
+            wheel_y: y,
+        })
+    }
+}
+
+pub struct CanvasWidgetBuilder<T: Send + 'static> {
+    canvas: CanvasWidget<T>,
+}
+
+impl<T: Send + 'static> CanvasWidgetBuilder<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            canvas: CanvasWidget::new(data),
+        }
+    }
+    pub fn on_draw<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Context, &mut T, CanvasFrame) -> GameResult + Send + Sync + 'static,
+    {
+        self.canvas.on_draw(f);
+        self
+    }
+    pub fn on_event<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut T, CanvasEvent, Rect) -> bool + Send + Sync + 'static,
+    {
+        self.canvas.on_event(f);
+        self
+    }
+    pub fn build(self) -> CanvasWidget<T> {
+        self.canvas
+    }
+}
@@ -0,0 +1,634 @@
+use crate::overlay::{OverlayId, Placement, PopupOptions};
+use crate::typeahead::TypeAhead;
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use async_std::task;
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::input::mouse;
+use ggez::nalgebra::Point2;
+use ggez::{timer, Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+
+#[derive(Clone, Default, Debug)]
+pub struct ComboBoxState {
+    pub items: Vec<String>,
+    pub selected: Option<usize>,
+    pub open: bool,
+    pub focused: bool,
+    pub rect: Rect,
+}
+
+impl ComboBoxState {
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selected.map(|n| self.items[n].as_str())
+    }
+}
+
+pub trait ComboBoxSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &ComboBoxState);
+    fn is_hot_area(&self, x: f32, y: f32) -> bool;
+    /// Rect the popup list is attached to
+    fn list_anchor(&self) -> Rect;
+    /// Size of the popup list with given number of items
+    fn list_size(&self, item_count: usize) -> (f32, f32);
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ComboListState {
+    pub items: Vec<String>,
+    pub highlighted: Option<usize>,
+    /// First visible item
+    pub scroll: usize,
+    pub rect: Rect,
+}
+
+pub trait ComboListSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &ComboListState);
+    fn item_at(&self, x: f32, y: f32) -> Option<usize>;
+    fn visible_count(&self) -> usize;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ComboBoxId(SrvId);
+
+impl From<ComboBoxId> for SrvId {
+    fn from(v: ComboBoxId) -> SrvId {
+        v.0
+    }
+}
+
+type SelectionHandler = Box<dyn Fn(&mut dyn Widget, usize) + Send + Sync>;
+
+enum ComboBoxOp {
+    GetItems,
+    SetItems(Vec<String>),
+    GetSelected,
+    GetSelectedText,
+    Select(Option<usize>),
+    OnSelectionChanged(SelectionHandler),
+    RemoveOnSelectionChanged(usize),
+    Choose(usize),
+    ListClosed,
+}
+
+impl Debug for ComboBoxOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComboBoxOp::GetItems => write!(f, "GetItems"),
+            ComboBoxOp::SetItems(items) => write!(f, "SetItems({:?})", items),
+            ComboBoxOp::GetSelected => write!(f, "GetSelected"),
+            ComboBoxOp::GetSelectedText => write!(f, "GetSelectedText"),
+            ComboBoxOp::Select(index) => write!(f, "Select({:?})", index),
+            ComboBoxOp::OnSelectionChanged(_) => write!(f, "OnSelectionChanged"),
+            ComboBoxOp::RemoveOnSelectionChanged(handler_id) => {
+                write!(f, "RemoveOnSelectionChanged({:?})", handler_id)
+            }
+            ComboBoxOp::Choose(index) => write!(f, "Choose({:?})", index),
+            ComboBoxOp::ListClosed => write!(f, "ListClosed"),
+        }
+    }
+}
+
+impl ComboBoxId {
+    pub async fn get_items(self) -> Vec<String> {
+        send_request(self.0, ComboBoxOp::GetItems).await.unwrap()
+    }
+    pub async fn set_items(self, items: Vec<String>) {
+        send_request(self.0, ComboBoxOp::SetItems(items))
+            .await
+            .unwrap()
+    }
+    pub async fn get_selected(self) -> Option<usize> {
+        send_request(self.0, ComboBoxOp::GetSelected).await.unwrap()
+    }
+    pub async fn get_selected_text(self) -> Option<String> {
+        send_request(self.0, ComboBoxOp::GetSelectedText)
+            .await
+            .unwrap()
+    }
+    pub async fn select(self, index: Option<usize>) {
+        send_request(self.0, ComboBoxOp::Select(index))
+            .await
+            .unwrap()
+    }
+    pub async fn on_selection_changed<F: Fn(&mut dyn Widget, usize) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, ComboBoxOp::OnSelectionChanged(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_selection_changed(self, handler_id: usize) {
+        send_request(self.0, ComboBoxOp::RemoveOnSelectionChanged(handler_id))
+            .await
+            .unwrap()
+    }
+    async fn choose(self, index: usize) {
+        send_request(self.0, ComboBoxOp::Choose(index))
+            .await
+            .unwrap()
+    }
+    async fn list_closed(self) {
+        send_request(self.0, ComboBoxOp::ListClosed).await.unwrap()
+    }
+}
+
+/// Popup list opened by `ComboBox` in the overlay
+pub struct ComboList<L: ComboListSkin> {
+    state: ComboListState,
+    skin: L,
+    reg: ServiceRegistration,
+    combo_box: ComboBoxId,
+    overlay: OverlayId,
+    pressed: bool,
+    closing: bool,
+    type_ahead: TypeAhead,
+}
+
+impl<L: ComboListSkin> ComboList<L> {
+    fn new(
+        combo_box: ComboBoxId,
+        overlay: OverlayId,
+        items: Vec<String>,
+        highlighted: Option<usize>,
+        rect: Rect,
+    ) -> Self {
+        let mut list = Self {
+            state: ComboListState {
+                items,
+                highlighted,
+                scroll: 0,
+                rect,
+            },
+            skin: L::default(),
+            reg: register_service(),
+            combo_box,
+            overlay,
+            pressed: false,
+            closing: false,
+            type_ahead: TypeAhead::new(),
+        };
+        list.skin.set_state(&list.state);
+        list.scroll_to_highlighted();
+        list
+    }
+    fn scroll_to_highlighted(&mut self) {
+        if let Some(highlighted) = self.state.highlighted {
+            let visible = self.skin.visible_count().max(1);
+            if highlighted < self.state.scroll {
+                self.state.scroll = highlighted
+            } else if highlighted >= self.state.scroll + visible {
+                self.state.scroll = highlighted + 1 - visible
+            }
+        }
+        self.skin.set_state(&self.state);
+    }
+    fn highlight(&mut self, index: usize) {
+        if !self.state.items.is_empty() {
+            self.state.highlighted = Some(index.min(self.state.items.len() - 1));
+            self.scroll_to_highlighted();
+        }
+    }
+    /// Overlay notifies the combo box when the list is closed
+    fn close(&mut self, chosen: Option<usize>) {
+        self.closing = true;
+        let (combo_box, overlay, id) = (self.combo_box, self.overlay, self.reg.id());
+        task::spawn(async move {
+            if let Some(index) = chosen {
+                combo_box.choose(index).await
+            }
+            overlay.close(id).await;
+        });
+    }
+    fn contains(&self, x: f32, y: f32) -> bool {
+        self.state.rect.contains(Point2::new(x, y))
+    }
+}
+
+impl<L: ComboListSkin + 'static> Widget for ComboList<L> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("items", self.state.items.len().to_string()),
+            ("highlighted", format!("{:?}", self.state.highlighted)),
+        ]
+    }
+}
+
+impl<L: ComboListSkin + 'static> EventHandlerProxy for ComboList<L> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        _button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if self.closing || !self.contains(x, y) {
+            return false;
+        }
+        self.pressed = true;
+        true
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if self.closing || !self.pressed {
+            return false;
+        }
+        self.pressed = false;
+        if button == MouseButton::Left {
+            if let Some(index) = self.skin.item_at(x, y) {
+                self.close(Some(index));
+            }
+        }
+        true
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> bool {
+        if self.closing || !self.contains(x, y) {
+            return false;
+        }
+        if let Some(index) = self.skin.item_at(x, y) {
+            self.state.highlighted = Some(index);
+        }
+        true
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) -> bool {
+        let pos = mouse::position(ctx);
+        if self.closing || !self.contains(pos.x, pos.y) {
+            return false;
+        }
+        let max_scroll = self
+            .state
+            .items
+            .len()
+            .saturating_sub(self.skin.visible_count());
+        if y > 0. {
+            self.state.scroll = self.state.scroll.saturating_sub(1);
+        } else if y < 0. {
+            self.state.scroll = (self.state.scroll + 1).min(max_scroll);
+        }
+        true
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        _keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        if self.closing {
+            return false;
+        }
+        let page = self.skin.visible_count().max(1);
+        let highlighted = self.state.highlighted;
+        match keycode {
+            KeyCode::Up => self.highlight(highlighted.map_or(0, |n| n.saturating_sub(1))),
+            KeyCode::Down => self.highlight(highlighted.map_or(0, |n| n + 1)),
+            KeyCode::PageUp => self.highlight(highlighted.map_or(0, |n| n.saturating_sub(page))),
+            KeyCode::PageDown => self.highlight(highlighted.map_or(0, |n| n + page)),
+            KeyCode::Home => self.highlight(0),
+            KeyCode::End => self.highlight(self.state.items.len()),
+            KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space => self.close(highlighted),
+            KeyCode::Escape | KeyCode::Tab => self.close(None),
+            _ => {}
+        }
+        // list is the only keyboard receiver while open
+        true
+    }
+
+    fn key_up_event(&mut self, _ctx: &mut Context, _keycode: KeyCode, _keymods: KeyMods) -> bool {
+        !self.closing
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        if self.closing {
+            return false;
+        }
+        if !character.is_control() && character != ' ' {
+            let now = timer::time_since_start(ctx);
+            let found =
+                self.type_ahead
+                    .input(now, character, &self.state.items, self.state.highlighted);
+            if let Some(index) = found {
+                self.highlight(index);
+            }
+        }
+        true
+    }
+}
+
+pub struct ComboBox<S: ComboBoxSkin, L: ComboListSkin> {
+    state: ComboBoxState,
+    skin: S,
+    reg: ServiceRegistration,
+    type_ahead: TypeAhead,
+    overlay: Option<OverlayId>,
+    on_selection_changed_handlers: IndexMap<usize, SelectionHandler>,
+    _list_skin: std::marker::PhantomData<L>,
+}
+
+impl<S: ComboBoxSkin, L: ComboListSkin> ComboBox<S, L> {
+    pub fn new() -> Self {
+        Self {
+            state: ComboBoxState::default(),
+            skin: S::default(),
+            reg: register_service(),
+            type_ahead: TypeAhead::new(),
+            overlay: None,
+            on_selection_changed_handlers: IndexMap::new(),
+            _list_skin: std::marker::PhantomData,
+        }
+    }
+    pub fn id(&self) -> ComboBoxId {
+        ComboBoxId(self.reg.id())
+    }
+    /// Drops selection if it's out of new items range
+    pub fn set_items(&mut self, items: Vec<String>) {
+        if self.state.selected.is_some_and(|n| n >= items.len()) {
+            self.state.selected = None
+        }
+        self.state.items = items;
+    }
+    pub fn get_items(&self) -> &[String] {
+        &self.state.items
+    }
+    pub fn select(&mut self, index: Option<usize>) {
+        self.state.selected = index.filter(|&n| n < self.state.items.len())
+    }
+    pub fn get_selected(&self) -> Option<usize> {
+        self.state.selected
+    }
+    pub fn get_selected_text(&self) -> Option<&str> {
+        self.state.selected_text()
+    }
+    /// Overlay where the list is opened. If it's not set, the overlay of the
+    /// `GuiRoot` owning the combo box is used; without any overlay
+    /// the list is not opened at all.
+    pub fn set_overlay(&mut self, overlay: Option<OverlayId>) {
+        self.overlay = overlay
+    }
+    pub fn get_overlay(&self) -> Option<OverlayId> {
+        self.overlay
+    }
+    pub fn on_selection_changed_box(&mut self, handler: SelectionHandler) -> usize {
+        add_to_indexmap(&mut self.on_selection_changed_handlers, handler)
+    }
+    pub fn on_selection_changed<F: Fn(&mut dyn Widget, usize) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_selection_changed_box(Box::new(f))
+    }
+    pub fn remove_on_selection_changed(&mut self, handler_id: usize) {
+        self.on_selection_changed_handlers.remove(&handler_id);
+    }
+}
+
+impl<S: ComboBoxSkin, L: ComboListSkin> Default for ComboBox<S, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ComboBoxSkin + 'static, L: ComboListSkin + 'static> ComboBox<S, L> {
+    /// Selects item from user input and notifies handlers if it was changed
+    fn change_selection(&mut self, index: usize) {
+        if index >= self.state.items.len() || Some(index) == self.state.selected {
+            return;
+        }
+        self.state.selected = Some(index);
+        let handlers = std::mem::replace(&mut self.on_selection_changed_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, index);
+        }
+        self.on_selection_changed_handlers = handlers;
+    }
+    fn open_list(&mut self) {
+        if self.state.open || self.state.items.is_empty() {
+            return;
+        }
+        if let Some(overlay) = self.overlay {
+            self.skin.set_state(&self.state);
+            let (w, h) = self.skin.list_size(self.state.items.len());
+            let list = ComboList::<L>::new(
+                self.id(),
+                overlay,
+                self.state.items.clone(),
+                self.state.selected,
+                Rect::new(0., 0., w, h),
+            );
+            let combo_box = self.id();
+            let options = PopupOptions::new()
+                .set_anchor(self.skin.list_anchor())
+                .set_placement(Placement::Below)
+                .set_close_on_outside_click(true)
+                .on_close(move |_| {
+                    task::spawn(async move { combo_box.list_closed().await });
+                });
+            self.state.open = true;
+            task::spawn(async move { overlay.open_with(list, options).await });
+        }
+    }
+}
+
+impl<S: ComboBoxSkin + 'static, L: ComboListSkin + 'static> Widget for ComboBox<S, L> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("items", self.state.items.len().to_string()),
+            ("selected", format!("{:?}", self.state.selected_text())),
+        ]
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.overlay.get_or_insert(overlay);
+    }
+}
+
+impl<S: ComboBoxSkin + 'static, L: ComboListSkin + 'static> EventHandlerProxy for ComboBox<S, L> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            ComboBoxOp::GetItems => Some(Box::new(self.get_items().to_vec())),
+            ComboBoxOp::SetItems(items) => {
+                self.set_items(items);
+                Some(Box::new(()))
+            }
+            ComboBoxOp::GetSelected => Some(Box::new(self.get_selected())),
+            ComboBoxOp::GetSelectedText => {
+                Some(Box::new(self.get_selected_text().map(|s| s.to_string())))
+            }
+            ComboBoxOp::Select(index) => {
+                self.select(index);
+                Some(Box::new(()))
+            }
+            ComboBoxOp::OnSelectionChanged(handler) => {
+                Some(Box::new(self.on_selection_changed_box(handler)))
+            }
+            ComboBoxOp::RemoveOnSelectionChanged(handler_id) => {
+                self.remove_on_selection_changed(handler_id);
+                Some(Box::new(()))
+            }
+            ComboBoxOp::Choose(index) => {
+                self.state.open = false;
+                self.change_selection(index);
+                Some(Box::new(()))
+            }
+            ComboBoxOp::ListClosed => {
+                self.state.open = false;
+                Some(Box::new(()))
+            }
+        });
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button != MouseButton::Left {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        if !self.skin.is_hot_area(x, y) {
+            self.state.focused = false;
+            return false;
+        }
+        self.state.focused = true;
+        self.open_list();
+        true
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        if !self.state.focused || self.state.open {
+            return false;
+        }
+        let last = self.state.items.len().saturating_sub(1);
+        let selected = self.state.selected;
+        match keycode {
+            KeyCode::Down if keymods.contains(KeyMods::ALT) => self.open_list(),
+            KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space | KeyCode::F4 => {
+                self.open_list()
+            }
+            KeyCode::Up => self.change_selection(selected.map_or(0, |n| n.saturating_sub(1))),
+            KeyCode::Down => self.change_selection(selected.map_or(0, |n| (n + 1).min(last))),
+            KeyCode::Home => self.change_selection(0),
+            KeyCode::End => self.change_selection(last),
+            _ => return false,
+        }
+        true
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        if !self.state.focused || self.state.open || character.is_control() || character == ' ' {
+            return false;
+        }
+        let now = timer::time_since_start(ctx);
+        let found = self
+            .type_ahead
+            .input(now, character, &self.state.items, self.state.selected);
+        if let Some(index) = found {
+            self.change_selection(index);
+        }
+        true
+    }
+}
+
+pub struct ComboBoxBuilder<S: ComboBoxSkin, L: ComboListSkin> {
+    combo_box: ComboBox<S, L>,
+}
+
+impl<S: ComboBoxSkin, L: ComboListSkin> Default for ComboBoxBuilder<S, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ComboBoxSkin, L: ComboListSkin> ComboBoxBuilder<S, L> {
+    pub fn new() -> Self {
+        Self {
+            combo_box: ComboBox::new(),
+        }
+    }
+    pub fn set_items<T: Into<String>, I: IntoIterator<Item = T>>(mut self, items: I) -> Self {
+        self.combo_box
+            .set_items(items.into_iter().map(|s| s.into()).collect());
+        self
+    }
+    pub fn select(mut self, index: usize) -> Self {
+        self.combo_box.select(Some(index));
+        self
+    }
+    pub fn set_overlay(mut self, overlay: OverlayId) -> Self {
+        self.combo_box.set_overlay(Some(overlay));
+        self
+    }
+    pub fn on_selection_changed<F: Fn(&mut dyn Widget, usize) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.combo_box.on_selection_changed(f);
+        self
+    }
+    pub fn build(self) -> ComboBox<S, L> {
+        self.combo_box
+    }
+}
@@ -0,0 +1,309 @@
+use crate::menu::{
+    activate_item, find_accelerator, ActivateHandler, MenuActivation, MenuChain, MenuItem, MenuSkin,
+};
+use crate::overlay::{OverlayId, Placement};
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use async_std::task;
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContextMenuId(SrvId);
+
+impl From<ContextMenuId> for SrvId {
+    fn from(v: ContextMenuId) -> SrvId {
+        v.0
+    }
+}
+
+enum ContextMenuOp {
+    GetItems,
+    SetItems(Vec<MenuItem>),
+    OnActivate(ActivateHandler),
+    RemoveOnActivate(usize),
+    Activate(Vec<usize>),
+}
+
+impl Debug for ContextMenuOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContextMenuOp::GetItems => write!(f, "GetItems"),
+            ContextMenuOp::SetItems(items) => write!(f, "SetItems({:?})", items),
+            ContextMenuOp::OnActivate(_) => write!(f, "OnActivate"),
+            ContextMenuOp::RemoveOnActivate(handler_id) => {
+                write!(f, "RemoveOnActivate({:?})", handler_id)
+            }
+            ContextMenuOp::Activate(path) => write!(f, "Activate({:?})", path),
+        }
+    }
+}
+
+impl ContextMenuId {
+    pub async fn get_items(self) -> Vec<MenuItem> {
+        send_request(self.0, ContextMenuOp::GetItems).await.unwrap()
+    }
+    pub async fn set_items(self, items: Vec<MenuItem>) {
+        send_request(self.0, ContextMenuOp::SetItems(items))
+            .await
+            .unwrap()
+    }
+    pub async fn on_activate<F: Fn(&mut dyn Widget, &MenuActivation) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, ContextMenuOp::OnActivate(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_activate(self, handler_id: usize) {
+        send_request(self.0, ContextMenuOp::RemoveOnActivate(handler_id))
+            .await
+            .unwrap()
+    }
+    async fn activate(self, path: Vec<usize>) {
+        send_request(self.0, ContextMenuOp::Activate(path))
+            .await
+            .unwrap()
+    }
+}
+
+/// Wraps a widget and opens popup menu on right click inside it.
+/// All other events are passed to the wrapped widget.
+pub struct ContextMenu<S: MenuSkin> {
+    child: Box<dyn Widget>,
+    items: Vec<MenuItem>,
+    rect: Rect,
+    reg: ServiceRegistration,
+    overlay: Option<OverlayId>,
+    on_activate_handlers: IndexMap<usize, ActivateHandler>,
+    _skin: PhantomData<S>,
+}
+
+impl<S: MenuSkin + 'static> ContextMenu<S> {
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self::new_box(Box::new(child))
+    }
+    pub fn new_box(child: Box<dyn Widget>) -> Self {
+        Self {
+            child,
+            items: Vec::new(),
+            rect: Rect::default(),
+            reg: register_service(),
+            overlay: None,
+            on_activate_handlers: IndexMap::new(),
+            _skin: PhantomData,
+        }
+    }
+    pub fn id(&self) -> ContextMenuId {
+        ContextMenuId(self.reg.id())
+    }
+    pub fn set_items(&mut self, items: Vec<MenuItem>) {
+        self.items = items
+    }
+    pub fn get_items(&self) -> &[MenuItem] {
+        &self.items
+    }
+    pub fn add_item(&mut self, item: MenuItem) {
+        self.items.push(item)
+    }
+    /// Overlay where the menu is opened. If it's not set, the overlay of the
+    /// `GuiRoot` owning the context menu is used; without any overlay the
+    /// menu is not opened at all.
+    pub fn set_overlay(&mut self, overlay: Option<OverlayId>) {
+        self.overlay = overlay
+    }
+    pub fn get_overlay(&self) -> Option<OverlayId> {
+        self.overlay
+    }
+    pub fn on_activate_box(&mut self, handler: ActivateHandler) -> usize {
+        add_to_indexmap(&mut self.on_activate_handlers, handler)
+    }
+    pub fn on_activate<F: Fn(&mut dyn Widget, &MenuActivation) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_activate_box(Box::new(f))
+    }
+    pub fn remove_on_activate(&mut self, handler_id: usize) {
+        self.on_activate_handlers.remove(&handler_id);
+    }
+    fn open_menu(&mut self, x: f32, y: f32) {
+        if self.items.is_empty() {
+            return;
+        }
+        if let Some(overlay) = self.overlay {
+            let id = self.id();
+            let chain = MenuChain::new(overlay, move |path| {
+                task::spawn(async move { id.activate(path).await });
+            });
+            chain.open::<S>(
+                0,
+                Vec::new(),
+                self.items.clone(),
+                None,
+                Rect::new(x, y, 0., 0.),
+                Placement::Below,
+            );
+        }
+    }
+    fn activate(&mut self, path: Vec<usize>) {
+        let activation = match activate_item(&mut self.items, path) {
+            Some(activation) => activation,
+            None => return,
+        };
+        let handlers = std::mem::replace(&mut self.on_activate_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, &activation);
+        }
+        self.on_activate_handlers = handlers;
+    }
+}
+
+impl<S: MenuSkin + 'static> Widget for ContextMenu<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.child.set_rect(rect);
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.rect)
+    }
+    fn children(&self) -> Vec<&dyn Widget> {
+        vec![self.child.as_ref()]
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        vec![self.child.as_mut()]
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![("items", self.items.len().to_string())]
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.overlay.get_or_insert(overlay);
+        self.child.attach_overlay(overlay)
+    }
+}
+
+impl<S: MenuSkin + 'static> EventHandlerProxy for ContextMenu<S> {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            ContextMenuOp::GetItems => Some(Box::new(self.get_items().to_vec())),
+            ContextMenuOp::SetItems(items) => {
+                self.set_items(items);
+                Some(Box::new(()))
+            }
+            ContextMenuOp::OnActivate(handler) => Some(Box::new(self.on_activate_box(handler))),
+            ContextMenuOp::RemoveOnActivate(handler_id) => {
+                self.remove_on_activate(handler_id);
+                Some(Box::new(()))
+            }
+            ContextMenuOp::Activate(path) => {
+                self.activate(path);
+                Some(Box::new(()))
+            }
+        });
+        self.child.update(ctx)
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.child.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button == MouseButton::Right && self.rect.contains(Point2::new(x, y)) {
+            self.open_menu(x, y);
+            return true;
+        }
+        self.child.mouse_button_down_event(ctx, button, x, y)
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        self.child.mouse_button_up_event(ctx, button, x, y)
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        self.child.mouse_motion_event(ctx, x, y, dx, dy)
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        self.child.mouse_wheel_event(ctx, x, y)
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        self.child.key_down_event(ctx, keycode, keymods, repeat)
+    }
+
+    fn shortcut_event(&mut self, _ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        match find_accelerator(&self.items, keycode, keymods) {
+            Some(path) => {
+                self.activate(path);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        self.child.key_up_event(ctx, keycode, keymods)
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        self.child.text_input_event(ctx, character)
+    }
+}
+
+pub struct ContextMenuBuilder<S: MenuSkin> {
+    context_menu: ContextMenu<S>,
+}
+
+impl<S: MenuSkin + 'static> ContextMenuBuilder<S> {
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self {
+            context_menu: ContextMenu::new(child),
+        }
+    }
+    pub fn add_item(mut self, item: MenuItem) -> Self {
+        self.context_menu.add_item(item);
+        self
+    }
+    pub fn set_overlay(mut self, overlay: OverlayId) -> Self {
+        self.context_menu.set_overlay(Some(overlay));
+        self
+    }
+    pub fn on_activate<F: Fn(&mut dyn Widget, &MenuActivation) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.context_menu.on_activate(f);
+        self
+    }
+    pub fn build(self) -> ContextMenu<S> {
+        self.context_menu
+    }
+}
@@ -1,8 +1,89 @@
 use crate::default_skin::button::DefaultButtonSkin;
+use crate::default_skin::combobox::{DefaultComboBoxSkin, DefaultComboListSkin};
+use crate::default_skin::dialog::DefaultDialogSkin;
+use crate::default_skin::image::DefaultImageSkin;
+use crate::default_skin::label::DefaultLabelSkin;
+use crate::default_skin::listview::DefaultListViewSkin;
+use crate::default_skin::menu::DefaultMenuSkin;
+use crate::default_skin::menubar::DefaultMenuBarSkin;
+use crate::default_skin::progressbar::DefaultProgressBarSkin;
+use crate::default_skin::slider::DefaultSliderSkin;
+use crate::default_skin::spinner::DefaultSpinnerSkin;
+use crate::default_skin::splitter::DefaultSplitterSkin;
+use crate::default_skin::table::DefaultTableSkin;
+use crate::default_skin::tabs::DefaultTabHeaderSkin;
+use crate::default_skin::textarea::DefaultTextAreaSkin;
+use crate::default_skin::textinput::DefaultTextInputSkin;
+use crate::default_skin::treeview::DefaultTreeViewSkin;
 
 pub mod button;
+pub mod combobox;
+pub mod dialog;
+pub mod image;
+pub mod label;
+pub mod listview;
+pub mod menu;
+pub mod menubar;
+pub mod progressbar;
+pub mod slider;
+pub mod spinner;
+pub mod splitter;
+pub mod table;
+pub mod tabs;
+pub mod textarea;
+pub mod textinput;
+pub mod treeview;
 
 pub type Button = crate::button::Button<DefaultButtonSkin>;
 pub type ButtonId = crate::button::ButtonId;
 pub type ButtonBuilder = crate::button::ButtonBuilder<DefaultButtonSkin>;
 pub type RibbonBuilder = crate::ribbon::RibbonBuilder;
+pub type Label = crate::label::Label<DefaultLabelSkin>;
+pub type LabelId = crate::label::LabelId;
+pub type LabelBuilder = crate::label::LabelBuilder<DefaultLabelSkin>;
+pub type TextInput = crate::textinput::TextInput<DefaultTextInputSkin>;
+pub type TextInputId = crate::textinput::TextInputId;
+pub type TextInputBuilder = crate::textinput::TextInputBuilder<DefaultTextInputSkin>;
+pub type TextArea = crate::textarea::TextArea<DefaultTextAreaSkin>;
+pub type TextAreaId = crate::textarea::TextAreaId;
+pub type TextAreaBuilder = crate::textarea::TextAreaBuilder<DefaultTextAreaSkin>;
+pub type Slider = crate::slider::Slider<DefaultSliderSkin>;
+pub type SliderId = crate::slider::SliderId;
+pub type SliderBuilder = crate::slider::SliderBuilder<DefaultSliderSkin>;
+pub type ProgressBar = crate::progressbar::ProgressBar<DefaultProgressBarSkin>;
+pub type ProgressBarId = crate::progressbar::ProgressBarId;
+pub type ProgressBarBuilder = crate::progressbar::ProgressBarBuilder<DefaultProgressBarSkin>;
+pub type Spinner = crate::spinner::Spinner<DefaultSpinnerSkin>;
+pub type SpinnerId = crate::spinner::SpinnerId;
+pub type SpinnerBuilder = crate::spinner::SpinnerBuilder<DefaultSpinnerSkin>;
+pub type Tabs = crate::tabs::Tabs<DefaultTabHeaderSkin>;
+pub type TabsId = crate::tabs::TabsId;
+pub type TabsBuilder = crate::tabs::TabsBuilder<DefaultTabHeaderSkin>;
+pub type ComboBox = crate::combobox::ComboBox<DefaultComboBoxSkin, DefaultComboListSkin>;
+pub type ComboBoxId = crate::combobox::ComboBoxId;
+pub type ComboBoxBuilder =
+    crate::combobox::ComboBoxBuilder<DefaultComboBoxSkin, DefaultComboListSkin>;
+pub type Dialog = crate::dialog::Dialog<DefaultDialogSkin, DefaultButtonSkin>;
+pub type DialogId = crate::dialog::DialogId;
+pub type DialogBuilder = crate::dialog::DialogBuilder<DefaultDialogSkin, DefaultButtonSkin>;
+pub type ContextMenu = crate::contextmenu::ContextMenu<DefaultMenuSkin>;
+pub type ContextMenuId = crate::contextmenu::ContextMenuId;
+pub type ContextMenuBuilder = crate::contextmenu::ContextMenuBuilder<DefaultMenuSkin>;
+pub type MenuBar = crate::menubar::MenuBar<DefaultMenuBarSkin, DefaultMenuSkin>;
+pub type MenuBarId = crate::menubar::MenuBarId;
+pub type MenuBarBuilder = crate::menubar::MenuBarBuilder<DefaultMenuBarSkin, DefaultMenuSkin>;
+pub type Splitter = crate::splitter::Splitter<DefaultSplitterSkin>;
+pub type SplitterId = crate::splitter::SplitterId;
+pub type SplitterBuilder = crate::splitter::SplitterBuilder<DefaultSplitterSkin>;
+pub type ListView = crate::listview::ListView<DefaultListViewSkin>;
+pub type ListViewId = crate::listview::ListViewId;
+pub type ListViewBuilder = crate::listview::ListViewBuilder<DefaultListViewSkin>;
+pub type TreeView = crate::treeview::TreeView<DefaultTreeViewSkin>;
+pub type TreeViewId = crate::treeview::TreeViewId;
+pub type TreeViewBuilder = crate::treeview::TreeViewBuilder<DefaultTreeViewSkin>;
+pub type Table = crate::table::Table<DefaultTableSkin>;
+pub type TableId = crate::table::TableId;
+pub type TableBuilder = crate::table::TableBuilder<DefaultTableSkin>;
+pub type Image = crate::image::Image<DefaultImageSkin>;
+pub type ImageId = crate::image::ImageId;
+pub type ImageBuilder = crate::image::ImageBuilder<DefaultImageSkin>;
@@ -1,4 +1,6 @@
 use crate::button::{ButtonMode, ButtonSkin, ButtonState};
+use crate::default_skin::image::draw_image_in_rect;
+use crate::image::ImageFit;
 use crate::EventHandlerProxy;
 use ggez::graphics::{self, Align, DrawMode, DrawParam, MeshBuilder, Rect, Text};
 use ggez::nalgebra::Point2;
@@ -29,6 +31,43 @@ fn button_rect(mut rect: Rect, touched: bool) -> Rect {
     rect
 }
 
+/// Splits rect to the square on the left and the rest of it.
+/// Without the rest or if the rect is too narrow the square takes whole rect.
+fn split_square(rect: Rect, has_rest: bool) -> (Rect, Option<Rect>) {
+    if !has_rest || rect.w <= rect.h {
+        (rect, None)
+    } else {
+        let square = Rect::new(rect.x, rect.y, rect.h, rect.h);
+        let rest = Rect::new(
+            rect.x + rect.h + MARGIN,
+            rect.y,
+            rect.w - rect.h - MARGIN,
+            rect.h,
+        );
+        (square, Some(rest))
+    }
+}
+
+impl DefaultButtonSkin {
+    /// Icon goes before the label or takes whole rect without label
+    fn content_rects(&self, rect: Rect) -> (Option<Rect>, Option<Rect>) {
+        match &self.state.icon {
+            Some(_) => {
+                let (icon_rect, label_rect) = split_square(rect, !self.state.label.is_empty());
+                (Some(icon_rect), label_rect)
+            }
+            None if self.state.label.is_empty() => (None, None),
+            None => (None, Some(rect)),
+        }
+    }
+    fn draw_icon(&self, ctx: &mut Context, icon_rect: Option<Rect>) -> GameResult {
+        match (&self.state.icon, icon_rect) {
+            (Some(icon), Some(rect)) => draw_image_in_rect(ctx, icon, rect, ImageFit::Contain),
+            _ => Ok(()),
+        }
+    }
+}
+
 impl EventHandlerProxy for DefaultButtonSkin {
     fn update(&mut self, _ctx: &mut Context) -> GameResult {
         Ok(())
@@ -42,14 +81,21 @@ impl EventHandlerProxy for DefaultButtonSkin {
                     .rectangle(DrawMode::fill(), rect, graphics::WHITE)
                     .build(ctx)?;
                 graphics::draw(ctx, &mesh, DrawParam::default())?;
-                let mut text = Text::new(self.state.label.clone());
-                text.set_bounds([rect.w, rect.h], Align::Center);
-                let tdh = (rect.h - text.height(ctx) as f32) / 2.;
-                graphics::draw(
-                    ctx,
-                    &text,
-                    (Point2::new(rect.x, rect.y + tdh), graphics::BLACK),
-                )
+                let (icon_rect, label_rect) = self.content_rects(rect);
+                self.draw_icon(ctx, icon_rect)?;
+                match label_rect {
+                    Some(rect) => {
+                        let mut text = Text::new(self.state.label.clone());
+                        text.set_bounds([rect.w, rect.h], Align::Center);
+                        let tdh = (rect.h - text.height(ctx) as f32) / 2.;
+                        graphics::draw(
+                            ctx,
+                            &text,
+                            (Point2::new(rect.x, rect.y + tdh), graphics::BLACK),
+                        )
+                    }
+                    None => Ok(()),
+                }
             }
             ButtonMode::Checkbox(checked) => {
                 let rect = base_rect(self.state.rect);
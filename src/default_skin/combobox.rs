@@ -0,0 +1,164 @@
+use crate::combobox::{ComboBoxSkin, ComboBoxState, ComboListSkin, ComboListState};
+use crate::default_skin::label::{draw_text_in_rect, TEXT_COLOR, TEXT_SCALE};
+use crate::label::{HorizontalAlign, TextOverflow, VerticalAlign};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+const ARROW_SIZE: f32 = 10.;
+const ITEM_HEIGHT: f32 = TEXT_SCALE + 8.;
+const MAX_VISIBLE_ITEMS: usize = 8;
+const LIST_BACKGROUND: Color = Color::new(0.15, 0.15, 0.15, 1.);
+const HIGHLIGHT_COLOR: Color = Color::new(0.3, 0.5, 1., 1.);
+
+fn base_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN * 2.;
+    rect
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultComboBoxSkin {
+    state: ComboBoxState,
+}
+
+impl EventHandlerProxy for DefaultComboBoxSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let rect = base_rect(self.state.rect);
+        let cx = rect.right() - MARGIN - ARROW_SIZE / 2.;
+        let cy = rect.y + rect.h / 2.;
+        let half = ARROW_SIZE / 2.;
+        // arrow points up while the list is open
+        let dy = if self.state.open {
+            -half / 2.
+        } else {
+            half / 2.
+        };
+        let mesh = MeshBuilder::new()
+            .rectangle(
+                DrawMode::stroke(if self.state.focused { 2. } else { 1. }),
+                rect,
+                graphics::WHITE,
+            )
+            .polygon(
+                DrawMode::fill(),
+                &[
+                    Point2::new(cx - half, cy - dy),
+                    Point2::new(cx + half, cy - dy),
+                    Point2::new(cx, cy + dy),
+                ],
+                graphics::WHITE,
+            )?
+            .build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        if let Some(text) = self.state.selected_text() {
+            let text_rect = Rect::new(
+                rect.x + MARGIN,
+                rect.y,
+                rect.w - ARROW_SIZE - MARGIN * 3.,
+                rect.h,
+            );
+            draw_text_in_rect(
+                ctx,
+                text,
+                text_rect,
+                HorizontalAlign::Left,
+                VerticalAlign::Center,
+                TextOverflow::Ellipsis,
+                TEXT_COLOR,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ComboBoxSkin for DefaultComboBoxSkin {
+    fn set_state(&mut self, state: &ComboBoxState) {
+        self.state = state.clone();
+    }
+    fn is_hot_area(&self, x: f32, y: f32) -> bool {
+        base_rect(self.state.rect).contains(Point2::new(x, y))
+    }
+    fn list_anchor(&self) -> Rect {
+        base_rect(self.state.rect)
+    }
+    fn list_size(&self, item_count: usize) -> (f32, f32) {
+        let rows = item_count.min(MAX_VISIBLE_ITEMS) as f32;
+        (base_rect(self.state.rect).w, rows * ITEM_HEIGHT)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultComboListSkin {
+    state: ComboListState,
+}
+
+impl DefaultComboListSkin {
+    fn item_rect(&self, index: usize) -> Rect {
+        let rect = self.state.rect;
+        let row = index as f32 - self.state.scroll as f32;
+        Rect::new(rect.x, rect.y + row * ITEM_HEIGHT, rect.w, ITEM_HEIGHT)
+    }
+}
+
+impl EventHandlerProxy for DefaultComboListSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let rect = self.state.rect;
+        let mut mb = MeshBuilder::new();
+        mb.rectangle(DrawMode::fill(), rect, LIST_BACKGROUND);
+        if let Some(highlighted) = self.state.highlighted {
+            let visible = self.state.scroll..self.state.scroll + self.visible_count();
+            if visible.contains(&highlighted) {
+                mb.rectangle(
+                    DrawMode::fill(),
+                    self.item_rect(highlighted),
+                    HIGHLIGHT_COLOR,
+                );
+            }
+        }
+        mb.rectangle(DrawMode::stroke(1.), rect, graphics::WHITE);
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        let end = (self.state.scroll + self.visible_count()).min(self.state.items.len());
+        for index in self.state.scroll..end {
+            let mut item_rect = self.item_rect(index);
+            item_rect.x += MARGIN;
+            item_rect.w -= MARGIN * 2.;
+            draw_text_in_rect(
+                ctx,
+                &self.state.items[index],
+                item_rect,
+                HorizontalAlign::Left,
+                VerticalAlign::Center,
+                TextOverflow::Ellipsis,
+                TEXT_COLOR,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ComboListSkin for DefaultComboListSkin {
+    fn set_state(&mut self, state: &ComboListState) {
+        self.state = state.clone();
+    }
+    fn item_at(&self, x: f32, y: f32) -> Option<usize> {
+        let rect = self.state.rect;
+        if !rect.contains(Point2::new(x, y)) {
+            return None;
+        }
+        let index = self.state.scroll + ((y - rect.y) / ITEM_HEIGHT) as usize;
+        if index < self.state.items.len() {
+            Some(index)
+        } else {
+            None
+        }
+    }
+    fn visible_count(&self) -> usize {
+        (self.state.rect.h / ITEM_HEIGHT).floor() as usize
+    }
+}
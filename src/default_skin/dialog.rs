@@ -0,0 +1,96 @@
+use crate::default_skin::button::DefaultButtonSkin;
+use crate::default_skin::label::{draw_text_in_rect, TEXT_COLOR};
+use crate::dialog::{self, DialogSkin, DialogState};
+use crate::label::{HorizontalAlign, TextOverflow, VerticalAlign};
+use crate::overlay::OverlayId;
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+const TITLE_HEIGHT: f32 = 30.;
+const BUTTONS_HEIGHT: f32 = 50.;
+const BACKGROUND: Color = Color::new(0.15, 0.15, 0.15, 1.);
+const TITLE_BACKGROUND: Color = Color::new(0.3, 0.5, 1., 1.);
+
+#[derive(Default, Debug)]
+pub struct DefaultDialogSkin {
+    state: DialogState,
+}
+
+impl DefaultDialogSkin {
+    fn title_rect(&self) -> Rect {
+        let rect = self.state.rect;
+        Rect::new(rect.x, rect.y, rect.w, TITLE_HEIGHT)
+    }
+}
+
+impl EventHandlerProxy for DefaultDialogSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let rect = self.state.rect;
+        let mesh = MeshBuilder::new()
+            .rectangle(DrawMode::fill(), rect, BACKGROUND)
+            .rectangle(DrawMode::fill(), self.title_rect(), TITLE_BACKGROUND)
+            .rectangle(DrawMode::stroke(1.), rect, graphics::WHITE)
+            .build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        let mut title_rect = self.title_rect();
+        title_rect.x += MARGIN;
+        title_rect.w -= MARGIN * 2.;
+        draw_text_in_rect(
+            ctx,
+            &self.state.title,
+            title_rect,
+            HorizontalAlign::Left,
+            VerticalAlign::Center,
+            TextOverflow::Ellipsis,
+            TEXT_COLOR,
+        )?;
+        if self.state.has_content {
+            return Ok(());
+        }
+        draw_text_in_rect(
+            ctx,
+            &self.state.text,
+            self.content_rect(),
+            HorizontalAlign::Center,
+            VerticalAlign::Center,
+            TextOverflow::Wrap,
+            TEXT_COLOR,
+        )
+    }
+}
+
+impl DialogSkin for DefaultDialogSkin {
+    fn set_state(&mut self, state: &DialogState) {
+        self.state = state.clone();
+    }
+    fn content_rect(&self) -> Rect {
+        let rect = self.state.rect;
+        Rect::new(
+            rect.x + MARGIN,
+            rect.y + TITLE_HEIGHT + MARGIN,
+            rect.w - MARGIN * 2.,
+            rect.h - TITLE_HEIGHT - BUTTONS_HEIGHT - MARGIN * 2.,
+        )
+    }
+    fn buttons_rect(&self) -> Rect {
+        let rect = self.state.rect;
+        Rect::new(
+            rect.x + MARGIN,
+            rect.bottom() - BUTTONS_HEIGHT,
+            rect.w - MARGIN * 2.,
+            BUTTONS_HEIGHT - MARGIN,
+        )
+    }
+}
+
+/// `crate::dialog::message` with the default skins
+pub async fn message<T: Into<String>>(overlay: OverlayId, text: T) {
+    dialog::message::<DefaultDialogSkin, DefaultButtonSkin, T>(overlay, text).await
+}
+
+/// `crate::dialog::confirm` with the default skins
+pub async fn confirm<T: Into<String>>(overlay: OverlayId, text: T) -> bool {
+    dialog::confirm::<DefaultDialogSkin, DefaultButtonSkin, T>(overlay, text).await
+}
@@ -0,0 +1,70 @@
+use crate::image::{ImageFit, ImageSkin, ImageState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, DrawParam, Rect};
+use ggez::nalgebra::{Point2, Vector2};
+use ggez::{Context, GameResult};
+
+/// Draws the image fitted into the rect. The part which doesn't fit is
+/// cropped through the source rect, so nothing is drawn outside of `rect`.
+pub(crate) fn draw_image_in_rect(
+    ctx: &mut Context,
+    image: &graphics::Image,
+    rect: Rect,
+    fit: ImageFit,
+) -> GameResult {
+    let (iw, ih) = (image.width() as f32, image.height() as f32);
+    if iw <= 0. || ih <= 0. || rect.w <= 0. || rect.h <= 0. {
+        return Ok(());
+    }
+    let (sx, sy) = match fit {
+        ImageFit::Stretch => (rect.w / iw, rect.h / ih),
+        ImageFit::Contain => {
+            let s = (rect.w / iw).min(rect.h / ih);
+            (s, s)
+        }
+        ImageFit::Cover => {
+            let s = (rect.w / iw).max(rect.h / ih);
+            (s, s)
+        }
+        ImageFit::Center => (1., 1.),
+    };
+    // visible fraction of the image and its offset along one axis
+    let fit_axis = |pos: f32, space: f32, size: f32| {
+        if size > space {
+            let fraction = space / size;
+            (pos, (1. - fraction) / 2., fraction)
+        } else {
+            (pos + (space - size) / 2., 0., 1.)
+        }
+    };
+    let (x, src_x, src_w) = fit_axis(rect.x, rect.w, iw * sx);
+    let (y, src_y, src_h) = fit_axis(rect.y, rect.h, ih * sy);
+    graphics::draw(
+        ctx,
+        image,
+        DrawParam::default()
+            .src(Rect::new(src_x, src_y, src_w, src_h))
+            .dest(Point2::new(x, y))
+            .scale(Vector2::new(sx, sy)),
+    )
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultImageSkin {
+    state: ImageState,
+}
+
+impl EventHandlerProxy for DefaultImageSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        match &self.state.image {
+            Some(image) => draw_image_in_rect(ctx, image, self.state.rect, self.state.fit),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ImageSkin for DefaultImageSkin {
+    fn set_state(&mut self, state: &ImageState) {
+        self.state = state.clone();
+    }
+}
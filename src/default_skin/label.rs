@@ -0,0 +1,113 @@
+use crate::label::{HorizontalAlign, LabelSkin, LabelState, TextOverflow, VerticalAlign};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Align, Color, DrawParam, Font, Rect, Scale, Text, TextFragment};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+pub(crate) const TEXT_SCALE: f32 = 16.;
+pub(crate) const TEXT_COLOR: Color = graphics::WHITE;
+const ELLIPSIS: &str = "\u{2026}";
+
+pub(crate) fn make_text(s: &str, color: Color) -> Text {
+    Text::new(
+        TextFragment::new(s)
+            .font(Font::default())
+            .scale(Scale::uniform(TEXT_SCALE))
+            .color(color),
+    )
+}
+
+pub(crate) fn text_width(ctx: &Context, s: &str) -> f32 {
+    make_text(s, TEXT_COLOR).width(ctx) as f32
+}
+
+/// Cuts the string and appends ellipsis if it doesn't fit into `max_width`
+pub(crate) fn ellipsize(ctx: &Context, s: &str, max_width: f32) -> String {
+    if text_width(ctx, s) <= max_width {
+        return s.to_string();
+    }
+    let chars: Vec<char> = s.chars().collect();
+    let (mut lo, mut hi) = (0, chars.len());
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        let candidate: String = chars[..mid].iter().collect::<String>() + ELLIPSIS;
+        if text_width(ctx, &candidate) <= max_width {
+            lo = mid
+        } else {
+            hi = mid - 1
+        }
+    }
+    chars[..lo].iter().collect::<String>() + ELLIPSIS
+}
+
+pub(crate) fn align_offset(free_space: f32, align: HorizontalAlign) -> f32 {
+    match align {
+        HorizontalAlign::Left => 0.,
+        HorizontalAlign::Center => free_space / 2.,
+        HorizontalAlign::Right => free_space,
+    }
+}
+
+pub(crate) fn draw_text_in_rect(
+    ctx: &mut Context,
+    s: &str,
+    rect: Rect,
+    horizontal_align: HorizontalAlign,
+    vertical_align: VerticalAlign,
+    overflow: TextOverflow,
+    color: Color,
+) -> GameResult {
+    let (text, x) = match overflow {
+        TextOverflow::Wrap => {
+            let mut text = make_text(s, color);
+            let align = match horizontal_align {
+                HorizontalAlign::Left => Align::Left,
+                HorizontalAlign::Center => Align::Center,
+                HorizontalAlign::Right => Align::Right,
+            };
+            text.set_bounds([rect.w, rect.h], align);
+            (text, rect.x)
+        }
+        TextOverflow::Ellipsis => {
+            let text = make_text(&ellipsize(ctx, s, rect.w), color);
+            let dx = align_offset(rect.w - text.width(ctx) as f32, horizontal_align);
+            (text, rect.x + dx)
+        }
+    };
+    let free_height = rect.h - text.height(ctx) as f32;
+    let dy = match vertical_align {
+        VerticalAlign::Top => 0.,
+        VerticalAlign::Center => free_height / 2.,
+        VerticalAlign::Bottom => free_height,
+    };
+    graphics::draw(
+        ctx,
+        &text,
+        DrawParam::default().dest(Point2::new(x, rect.y + dy)),
+    )
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultLabelSkin {
+    state: LabelState,
+}
+
+impl EventHandlerProxy for DefaultLabelSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        draw_text_in_rect(
+            ctx,
+            &self.state.text,
+            self.state.rect,
+            self.state.horizontal_align,
+            self.state.vertical_align,
+            self.state.overflow,
+            TEXT_COLOR,
+        )
+    }
+}
+
+impl LabelSkin for DefaultLabelSkin {
+    fn set_state(&mut self, state: &LabelState) {
+        self.state = state.clone();
+    }
+}
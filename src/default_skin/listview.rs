@@ -0,0 +1,121 @@
+use crate::default_skin::label::{draw_text_in_rect, TEXT_COLOR, TEXT_SCALE};
+use crate::label::{HorizontalAlign, TextOverflow, VerticalAlign};
+use crate::listview::{ListViewSkin, ListViewState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+const ITEM_HEIGHT: f32 = TEXT_SCALE + 8.;
+const SCROLLBAR_WIDTH: f32 = 4.;
+const SELECTION_COLOR: Color = Color::new(0.3, 0.5, 1., 1.);
+const SCROLLBAR_COLOR: Color = Color::new(0.5, 0.5, 0.5, 1.);
+
+fn base_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN * 2.;
+    rect
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultListViewSkin {
+    state: ListViewState,
+}
+
+impl DefaultListViewSkin {
+    fn item_rect(&self, index: usize) -> Rect {
+        let rect = base_rect(self.state.rect);
+        let row = index as f32 - self.state.scroll as f32;
+        Rect::new(
+            rect.x,
+            rect.y + row * ITEM_HEIGHT,
+            rect.w - SCROLLBAR_WIDTH,
+            ITEM_HEIGHT,
+        )
+    }
+    fn visible_range(&self) -> std::ops::Range<usize> {
+        let end = (self.state.scroll + self.visible_count()).min(self.state.items.len());
+        self.state.scroll.min(end)..end
+    }
+}
+
+impl EventHandlerProxy for DefaultListViewSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let rect = base_rect(self.state.rect);
+        let mut mb = MeshBuilder::new();
+        for index in self.visible_range() {
+            if self.state.is_selected(index) {
+                mb.rectangle(DrawMode::fill(), self.item_rect(index), SELECTION_COLOR);
+            }
+        }
+        if let Some(current) = self.state.current {
+            if self.state.focused && self.visible_range().contains(&current) {
+                mb.rectangle(
+                    DrawMode::stroke(1.),
+                    self.item_rect(current),
+                    graphics::WHITE,
+                );
+            }
+        }
+        let count = self.state.items.len();
+        let visible = self.visible_count();
+        if count > visible {
+            let h = rect.h * visible as f32 / count as f32;
+            let y = rect.y + rect.h * self.state.scroll as f32 / count as f32;
+            mb.rectangle(
+                DrawMode::fill(),
+                Rect::new(rect.right() - SCROLLBAR_WIDTH, y, SCROLLBAR_WIDTH, h),
+                SCROLLBAR_COLOR,
+            );
+        }
+        mb.rectangle(
+            DrawMode::stroke(if self.state.focused { 2. } else { 1. }),
+            rect,
+            graphics::WHITE,
+        );
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        for index in self.visible_range() {
+            let mut item_rect = self.item_rect(index);
+            item_rect.x += MARGIN;
+            item_rect.w -= MARGIN * 2.;
+            draw_text_in_rect(
+                ctx,
+                &self.state.items[index],
+                item_rect,
+                HorizontalAlign::Left,
+                VerticalAlign::Center,
+                TextOverflow::Ellipsis,
+                TEXT_COLOR,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ListViewSkin for DefaultListViewSkin {
+    fn set_state(&mut self, state: &ListViewState) {
+        self.state = state.clone();
+    }
+    fn is_hot_area(&self, x: f32, y: f32) -> bool {
+        base_rect(self.state.rect).contains(Point2::new(x, y))
+    }
+    fn item_at(&self, x: f32, y: f32) -> Option<usize> {
+        let rect = base_rect(self.state.rect);
+        if !rect.contains(Point2::new(x, y)) {
+            return None;
+        }
+        let index = self.state.scroll + ((y - rect.y) / ITEM_HEIGHT) as usize;
+        if self.visible_range().contains(&index) {
+            Some(index)
+        } else {
+            None
+        }
+    }
+    fn visible_count(&self) -> usize {
+        (base_rect(self.state.rect).h / ITEM_HEIGHT).floor().max(0.) as usize
+    }
+}
@@ -0,0 +1,139 @@
+use crate::button::ButtonMode;
+use crate::default_skin::label::{draw_text_in_rect, TEXT_COLOR, TEXT_SCALE};
+use crate::label::{HorizontalAlign, TextOverflow, VerticalAlign};
+use crate::menu::{MenuItem, MenuSkin, MenuState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+const MENU_WIDTH: f32 = 250.;
+const ITEM_HEIGHT: f32 = TEXT_SCALE + 8.;
+const SEPARATOR_HEIGHT: f32 = 8.;
+const MARK_SIZE: f32 = 10.;
+const BACKGROUND: Color = Color::new(0.15, 0.15, 0.15, 1.);
+const HIGHLIGHT_COLOR: Color = Color::new(0.3, 0.5, 1., 1.);
+const SEPARATOR_COLOR: Color = Color::new(0.5, 0.5, 0.5, 1.);
+const HINT_COLOR: Color = Color::new(0.7, 0.7, 0.7, 1.);
+
+fn item_height(item: &MenuItem) -> f32 {
+    if item.separator {
+        SEPARATOR_HEIGHT
+    } else {
+        ITEM_HEIGHT
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultMenuSkin {
+    state: MenuState,
+}
+
+impl EventHandlerProxy for DefaultMenuSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let rect = self.state.rect;
+        let mut mb = MeshBuilder::new();
+        mb.rectangle(DrawMode::fill(), rect, BACKGROUND);
+        if let Some(highlighted) = self.state.highlighted {
+            mb.rectangle(
+                DrawMode::fill(),
+                self.item_rect(highlighted),
+                HIGHLIGHT_COLOR,
+            );
+        }
+        mb.rectangle(DrawMode::stroke(1.), rect, graphics::WHITE);
+        for (index, item) in self.state.items.iter().enumerate() {
+            let item_rect = self.item_rect(index);
+            let cy = item_rect.y + item_rect.h / 2.;
+            if item.separator {
+                mb.line(
+                    &[
+                        Point2::new(item_rect.x + MARGIN, cy),
+                        Point2::new(item_rect.right() - MARGIN, cy),
+                    ],
+                    1.,
+                    SEPARATOR_COLOR,
+                )?;
+                continue;
+            }
+            let half = MARK_SIZE / 2.;
+            if let ButtonMode::Checkbox(true) = item.mode {
+                let cx = item_rect.x + MARGIN + half;
+                mb.line(
+                    &[
+                        Point2::new(cx - half, cy),
+                        Point2::new(cx - half / 3., cy + half),
+                        Point2::new(cx + half, cy - half),
+                    ],
+                    2.,
+                    graphics::WHITE,
+                )?;
+            }
+            if item.is_submenu() {
+                let cx = item_rect.right() - MARGIN - half;
+                mb.polygon(
+                    DrawMode::fill(),
+                    &[
+                        Point2::new(cx - half / 2., cy - half),
+                        Point2::new(cx - half / 2., cy + half),
+                        Point2::new(cx + half / 2., cy),
+                    ],
+                    graphics::WHITE,
+                )?;
+            }
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        for (index, item) in self.state.items.iter().enumerate() {
+            if item.separator {
+                continue;
+            }
+            let mut text_rect = self.item_rect(index);
+            text_rect.x += MARK_SIZE + MARGIN * 2.;
+            text_rect.w -= (MARK_SIZE + MARGIN * 2.) * 2.;
+            draw_text_in_rect(
+                ctx,
+                &item.display_label(),
+                text_rect,
+                HorizontalAlign::Left,
+                VerticalAlign::Center,
+                TextOverflow::Ellipsis,
+                TEXT_COLOR,
+            )?;
+            if let Some(accelerator) = item.accelerator {
+                draw_text_in_rect(
+                    ctx,
+                    &accelerator.to_string(),
+                    text_rect,
+                    HorizontalAlign::Right,
+                    VerticalAlign::Center,
+                    TextOverflow::Ellipsis,
+                    HINT_COLOR,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MenuSkin for DefaultMenuSkin {
+    fn set_state(&mut self, state: &MenuState) {
+        self.state = state.clone();
+    }
+    fn item_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..self.state.items.len()).find(|index| self.item_rect(*index).contains(Point2::new(x, y)))
+    }
+    fn item_rect(&self, index: usize) -> Rect {
+        let rect = self.state.rect;
+        let y = self.state.items[..index]
+            .iter()
+            .map(item_height)
+            .sum::<f32>();
+        let h = self.state.items.get(index).map_or(ITEM_HEIGHT, item_height);
+        Rect::new(rect.x, rect.y + y, rect.w, h)
+    }
+    fn menu_size(&self, items: &[MenuItem]) -> (f32, f32) {
+        (MENU_WIDTH, items.iter().map(item_height).sum())
+    }
+}
@@ -0,0 +1,75 @@
+use crate::default_skin::label::{draw_text_in_rect, text_width, TEXT_COLOR};
+use crate::label::{HorizontalAlign, TextOverflow, VerticalAlign};
+use crate::menubar::{MenuBarSkin, MenuBarState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const PADDING: f32 = 10.;
+/// Title width used until the text is measured on first draw
+const MIN_TITLE_WIDTH: f32 = 60.;
+const BACKGROUND: Color = Color::new(0.15, 0.15, 0.15, 1.);
+const HOVER_COLOR: Color = Color::new(0.3, 0.3, 0.3, 1.);
+const HIGHLIGHT_COLOR: Color = Color::new(0.3, 0.5, 1., 1.);
+
+#[derive(Default, Debug)]
+pub struct DefaultMenuBarSkin {
+    state: MenuBarState,
+    title_widths: Vec<f32>,
+}
+
+impl DefaultMenuBarSkin {
+    fn title_width(&self, index: usize) -> f32 {
+        self.title_widths
+            .get(index)
+            .copied()
+            .unwrap_or(MIN_TITLE_WIDTH)
+    }
+}
+
+impl EventHandlerProxy for DefaultMenuBarSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.title_widths = self
+            .state
+            .items
+            .iter()
+            .map(|item| text_width(ctx, &item.display_label()) + PADDING * 2.)
+            .collect();
+        let mut mb = MeshBuilder::new();
+        mb.rectangle(DrawMode::fill(), self.state.rect, BACKGROUND);
+        if let Some(open) = self.state.open {
+            mb.rectangle(DrawMode::fill(), self.item_rect(open), HIGHLIGHT_COLOR);
+        } else if let Some(hovered) = self.state.hovered {
+            mb.rectangle(DrawMode::fill(), self.item_rect(hovered), HOVER_COLOR);
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        for (index, item) in self.state.items.iter().enumerate() {
+            draw_text_in_rect(
+                ctx,
+                &item.display_label(),
+                self.item_rect(index),
+                HorizontalAlign::Center,
+                VerticalAlign::Center,
+                TextOverflow::Ellipsis,
+                TEXT_COLOR,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl MenuBarSkin for DefaultMenuBarSkin {
+    fn set_state(&mut self, state: &MenuBarState) {
+        self.state = state.clone();
+    }
+    fn item_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..self.state.items.len()).find(|index| self.item_rect(*index).contains(Point2::new(x, y)))
+    }
+    fn item_rect(&self, index: usize) -> Rect {
+        let rect = self.state.rect;
+        let x = (0..index).map(|i| self.title_width(i)).sum::<f32>();
+        Rect::new(rect.x + x, rect.y, self.title_width(index), rect.h)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::default_skin::label::{make_text, TEXT_COLOR};
+use crate::progressbar::{ProgressBarSkin, ProgressBarState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+/// Part of the bar filled in indeterminate mode
+const INDETERMINATE_WIDTH: f32 = 0.25;
+const BAR_COLOR: Color = Color::new(0.3, 0.5, 1., 1.);
+
+fn base_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN * 2.;
+    rect
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultProgressBarSkin {
+    state: ProgressBarState,
+}
+
+impl EventHandlerProxy for DefaultProgressBarSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let rect = base_rect(self.state.rect);
+        let (from, to) = match self.state.fraction {
+            Some(fraction) => (0., fraction),
+            None => {
+                // block runs from outside left edge to outside right edge
+                let x = self.state.phase * (1. + INDETERMINATE_WIDTH) - INDETERMINATE_WIDTH;
+                (x.max(0.), (x + INDETERMINATE_WIDTH).min(1.))
+            }
+        };
+        let mut mb = MeshBuilder::new();
+        mb.rectangle(DrawMode::stroke(1.), rect, graphics::WHITE);
+        if to > from {
+            mb.rectangle(
+                DrawMode::fill(),
+                Rect::new(rect.x + rect.w * from, rect.y, rect.w * (to - from), rect.h),
+                BAR_COLOR,
+            );
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        if let Some(fraction) = self.state.fraction {
+            let text = make_text(&format!("{:.0}%", fraction * 100.), TEXT_COLOR);
+            let (w, h) = text.dimensions(ctx);
+            let x = rect.x + (rect.w - w as f32) / 2.;
+            let y = rect.y + (rect.h - h as f32) / 2.;
+            graphics::draw(ctx, &text, DrawParam::default().dest(Point2::new(x, y)))?;
+        }
+        Ok(())
+    }
+}
+
+impl ProgressBarSkin for DefaultProgressBarSkin {
+    fn set_state(&mut self, state: &ProgressBarState) {
+        self.state = state.clone();
+    }
+}
@@ -0,0 +1,119 @@
+use crate::ribbon::RibbonOrientation;
+use crate::slider::{SliderSkin, SliderState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+const THUMB_SIZE: f32 = 16.;
+const TRACK_WIDTH: f32 = 4.;
+const TRACK_COLOR: Color = Color::new(0.5, 0.5, 0.5, 1.);
+
+fn base_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN * 2.;
+    rect
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultSliderSkin {
+    state: SliderState,
+}
+
+impl DefaultSliderSkin {
+    /// Ends of the line which thumb center moves along, from min to max
+    fn track(&self) -> (Point2<f32>, Point2<f32>) {
+        let rect = base_rect(self.state.rect);
+        let half = THUMB_SIZE / 2.;
+        match self.state.orientation {
+            RibbonOrientation::Horizontal => {
+                let y = rect.y + rect.h / 2.;
+                (
+                    Point2::new(rect.x + half, y),
+                    Point2::new(rect.right() - half, y),
+                )
+            }
+            RibbonOrientation::Vertical => {
+                let x = rect.x + rect.w / 2.;
+                (
+                    Point2::new(x, rect.bottom() - half),
+                    Point2::new(x, rect.y + half),
+                )
+            }
+        }
+    }
+    fn thumb_rect(&self) -> Rect {
+        let (from, to) = self.track();
+        let f = self.state.fraction();
+        let x = from.x + (to.x - from.x) * f;
+        let y = from.y + (to.y - from.y) * f;
+        Rect::new(
+            x - THUMB_SIZE / 2.,
+            y - THUMB_SIZE / 2.,
+            THUMB_SIZE,
+            THUMB_SIZE,
+        )
+    }
+}
+
+impl EventHandlerProxy for DefaultSliderSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let (from, to) = self.track();
+        let track = Rect::new(
+            from.x.min(to.x) - TRACK_WIDTH / 2.,
+            from.y.min(to.y) - TRACK_WIDTH / 2.,
+            (to.x - from.x).abs() + TRACK_WIDTH,
+            (to.y - from.y).abs() + TRACK_WIDTH,
+        );
+        let thumb = self.thumb_rect();
+        let mesh = MeshBuilder::new()
+            .rectangle(DrawMode::fill(), track, TRACK_COLOR)
+            .rectangle(
+                if self.state.dragging {
+                    DrawMode::stroke(2.)
+                } else {
+                    DrawMode::fill()
+                },
+                thumb,
+                graphics::WHITE,
+            )
+            .build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        if self.state.focused {
+            let focus = MeshBuilder::new()
+                .rectangle(
+                    DrawMode::stroke(1.),
+                    base_rect(self.state.rect),
+                    TRACK_COLOR,
+                )
+                .build(ctx)?;
+            graphics::draw(ctx, &focus, DrawParam::default())?;
+        }
+        Ok(())
+    }
+}
+
+impl SliderSkin for DefaultSliderSkin {
+    fn set_state(&mut self, state: &SliderState) {
+        self.state = state.clone();
+    }
+    fn is_hot_area(&self, x: f32, y: f32) -> bool {
+        base_rect(self.state.rect).contains(Point2::new(x, y))
+    }
+    fn is_thumb_area(&self, x: f32, y: f32) -> bool {
+        self.thumb_rect().contains(Point2::new(x, y))
+    }
+    fn fraction_at(&self, x: f32, y: f32) -> f32 {
+        let (from, to) = self.track();
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let len2 = dx * dx + dy * dy;
+        if len2 > 0. {
+            ((x - from.x) * dx + (y - from.y) * dy) / len2
+        } else {
+            0.
+        }
+    }
+}
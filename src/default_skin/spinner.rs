@@ -0,0 +1,55 @@
+use crate::spinner::{SpinnerSkin, SpinnerState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+use std::f32::consts::PI;
+
+const MARGIN: f32 = 5.;
+const DOTS: usize = 8;
+
+#[derive(Default, Debug)]
+pub struct DefaultSpinnerSkin {
+    state: SpinnerState,
+}
+
+impl EventHandlerProxy for DefaultSpinnerSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        if !self.state.active {
+            return Ok(());
+        }
+        let rect = self.state.rect;
+        let radius = (rect.w.min(rect.h) / 2. - MARGIN).max(0.);
+        let dot_radius = radius / 6.;
+        if dot_radius <= 0. {
+            return Ok(());
+        }
+        let center = Point2::new(rect.x + rect.w / 2., rect.y + rect.h / 2.);
+        // the brightest dot moves around, others fade behind it
+        let head = (self.state.phase * DOTS as f32) as usize % DOTS;
+        let mut mb = MeshBuilder::new();
+        for n in 0..DOTS {
+            let angle = 2. * PI * n as f32 / DOTS as f32;
+            let age = (head + DOTS - n) % DOTS;
+            let alpha = 1. - age as f32 / DOTS as f32;
+            mb.circle(
+                DrawMode::fill(),
+                Point2::new(
+                    center.x + (radius - dot_radius) * angle.cos(),
+                    center.y + (radius - dot_radius) * angle.sin(),
+                ),
+                dot_radius,
+                0.2,
+                Color::new(1., 1., 1., alpha),
+            );
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())
+    }
+}
+
+impl SpinnerSkin for DefaultSpinnerSkin {
+    fn set_state(&mut self, state: &SpinnerState) {
+        self.state = state.clone();
+    }
+}
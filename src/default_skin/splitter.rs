@@ -0,0 +1,54 @@
+use crate::splitter::{SplitterSkin, SplitterState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const DIVIDER_SIZE: f32 = 6.;
+const DIVIDER_COLOR: Color = Color::new(0.3, 0.3, 0.3, 1.);
+const HIGHLIGHT_COLOR: Color = Color::new(0.3, 0.5, 1., 1.);
+
+#[derive(Default, Debug)]
+pub struct DefaultSplitterSkin {
+    state: SplitterState,
+}
+
+impl EventHandlerProxy for DefaultSplitterSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        if self.state.positions.is_empty() {
+            return Ok(());
+        }
+        let active = self.state.dragging.or(self.state.hovered);
+        let mut mb = MeshBuilder::new();
+        for index in 0..self.state.positions.len() {
+            let color = if active == Some(index) {
+                HIGHLIGHT_COLOR
+            } else {
+                DIVIDER_COLOR
+            };
+            mb.rectangle(
+                DrawMode::fill(),
+                self.state.divider_rect(index, DIVIDER_SIZE),
+                color,
+            );
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())
+    }
+}
+
+impl SplitterSkin for DefaultSplitterSkin {
+    fn set_state(&mut self, state: &SplitterState) {
+        self.state = state.clone();
+    }
+    fn divider_size(&self) -> f32 {
+        DIVIDER_SIZE
+    }
+    fn divider_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..self.state.positions.len()).find(|index| {
+            self.state
+                .divider_rect(*index, DIVIDER_SIZE)
+                .contains(Point2::new(x, y))
+        })
+    }
+}
@@ -0,0 +1,221 @@
+use crate::default_skin::label::{draw_text_in_rect, TEXT_COLOR, TEXT_SCALE};
+use crate::label::{HorizontalAlign, TextOverflow, VerticalAlign};
+use crate::table::{SortOrder, TableSkin, TableState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+const ITEM_HEIGHT: f32 = TEXT_SCALE + 8.;
+const HEADER_HEIGHT: f32 = ITEM_HEIGHT + 4.;
+/// Distance from the column edge where dragging resizes the column
+const DIVIDER_HOT_WIDTH: f32 = 4.;
+const ARROW_SIZE: f32 = 8.;
+const SCROLLBAR_WIDTH: f32 = 4.;
+const HEADER_COLOR: Color = Color::new(0.25, 0.25, 0.25, 1.);
+const SELECTION_COLOR: Color = Color::new(0.3, 0.5, 1., 1.);
+const SCROLLBAR_COLOR: Color = Color::new(0.5, 0.5, 0.5, 1.);
+
+fn base_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN * 2.;
+    rect
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultTableSkin {
+    state: TableState,
+}
+
+impl DefaultTableSkin {
+    fn header_rect(&self) -> Rect {
+        let rect = base_rect(self.state.rect);
+        Rect::new(rect.x, rect.y, rect.w - SCROLLBAR_WIDTH, HEADER_HEIGHT)
+    }
+    fn body_rect(&self) -> Rect {
+        let rect = base_rect(self.state.rect);
+        Rect::new(
+            rect.x,
+            rect.y + HEADER_HEIGHT,
+            rect.w - SCROLLBAR_WIDTH,
+            rect.h - HEADER_HEIGHT,
+        )
+    }
+    /// Left and right edges of the column, may go beyond the table
+    fn column_span(&self, column: usize) -> (f32, f32) {
+        let left = self.header_rect().x
+            + self.state.columns[..column]
+                .iter()
+                .map(|c| c.width)
+                .sum::<f32>();
+        (left, left + self.state.columns[column].width)
+    }
+    /// Part of the cell inside the table, `None` if the column is hidden
+    fn cell_rect(&self, column: usize, y: f32, h: f32) -> Option<Rect> {
+        let (left, right) = self.column_span(column);
+        let right = right.min(self.header_rect().right());
+        if left < right {
+            Some(Rect::new(left, y, right - left, h))
+        } else {
+            None
+        }
+    }
+    fn draw_cell_text(&self, ctx: &mut Context, text: &str, mut rect: Rect) -> GameResult {
+        rect.x += MARGIN;
+        rect.w -= MARGIN * 2.;
+        if rect.w <= 0. {
+            return Ok(());
+        }
+        draw_text_in_rect(
+            ctx,
+            text,
+            rect,
+            HorizontalAlign::Left,
+            VerticalAlign::Center,
+            TextOverflow::Ellipsis,
+            TEXT_COLOR,
+        )
+    }
+}
+
+impl EventHandlerProxy for DefaultTableSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let rect = base_rect(self.state.rect);
+        let header = self.header_rect();
+        let body = self.body_rect();
+        let mut mb = MeshBuilder::new();
+        mb.rectangle(DrawMode::fill(), header, HEADER_COLOR);
+        for (n, row) in self.state.rows.iter().enumerate() {
+            if row.selected {
+                let y = body.y + n as f32 * ITEM_HEIGHT;
+                mb.rectangle(
+                    DrawMode::fill(),
+                    Rect::new(body.x, y, body.w, ITEM_HEIGHT),
+                    SELECTION_COLOR,
+                );
+            }
+        }
+        for column in 0..self.state.columns.len() {
+            let (_, right) = self.column_span(column);
+            if right > header.right() {
+                break;
+            }
+            let width = if self.state.resizing == Some(column) {
+                2.
+            } else {
+                1.
+            };
+            mb.line(
+                &[
+                    Point2::new(right, header.y),
+                    Point2::new(right, rect.bottom()),
+                ],
+                width,
+                graphics::WHITE,
+            )?;
+        }
+        if let Some((column, sort_order)) = self.state.sort {
+            if let Some(cell) = self.cell_rect(column, header.y, header.h) {
+                let cx = cell.right() - MARGIN - ARROW_SIZE / 2.;
+                let cy = cell.y + cell.h / 2.;
+                let d = ARROW_SIZE / 2.;
+                let points = match sort_order {
+                    SortOrder::Ascending => [
+                        Point2::new(cx - d, cy + d / 2.),
+                        Point2::new(cx + d, cy + d / 2.),
+                        Point2::new(cx, cy - d / 2.),
+                    ],
+                    SortOrder::Descending => [
+                        Point2::new(cx - d, cy - d / 2.),
+                        Point2::new(cx + d, cy - d / 2.),
+                        Point2::new(cx, cy + d / 2.),
+                    ],
+                };
+                if cell.w > ARROW_SIZE + MARGIN * 2. {
+                    mb.polygon(DrawMode::fill(), &points, TEXT_COLOR)?;
+                }
+            }
+        }
+        let count = self.state.row_count;
+        let visible = self.visible_count();
+        if count > visible {
+            let h = body.h * visible as f32 / count as f32;
+            let y = body.y + body.h * self.state.scroll as f32 / count as f32;
+            mb.rectangle(
+                DrawMode::fill(),
+                Rect::new(rect.right() - SCROLLBAR_WIDTH, y, SCROLLBAR_WIDTH, h),
+                SCROLLBAR_COLOR,
+            );
+        }
+        mb.rectangle(
+            DrawMode::stroke(if self.state.focused { 2. } else { 1. }),
+            rect,
+            graphics::WHITE,
+        );
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        for (column, c) in self.state.columns.iter().enumerate() {
+            if let Some(mut cell) = self.cell_rect(column, header.y, header.h) {
+                if self.state.sort.is_some_and(|(sorted, _)| sorted == column) {
+                    cell.w -= ARROW_SIZE + MARGIN;
+                }
+                self.draw_cell_text(ctx, &c.title, cell)?;
+            }
+        }
+        for (n, row) in self.state.rows.iter().enumerate() {
+            let y = body.y + n as f32 * ITEM_HEIGHT;
+            for (column, text) in row.cells.iter().enumerate() {
+                if let Some(cell) = self.cell_rect(column, y, ITEM_HEIGHT) {
+                    self.draw_cell_text(ctx, text, cell)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TableSkin for DefaultTableSkin {
+    fn set_state(&mut self, state: &TableState) {
+        self.state = state.clone();
+    }
+    fn is_hot_area(&self, x: f32, y: f32) -> bool {
+        base_rect(self.state.rect).contains(Point2::new(x, y))
+    }
+    fn header_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.header_rect().contains(Point2::new(x, y)) {
+            return None;
+        }
+        (0..self.state.columns.len()).find(|&column| {
+            let (left, right) = self.column_span(column);
+            x >= left && x < right
+        })
+    }
+    fn divider_at(&self, x: f32, y: f32) -> Option<usize> {
+        let header = self.header_rect();
+        if y < header.y || y >= header.bottom() {
+            return None;
+        }
+        (0..self.state.columns.len()).find(|&column| {
+            let (_, right) = self.column_span(column);
+            (x - right).abs() <= DIVIDER_HOT_WIDTH && right <= header.right() + DIVIDER_HOT_WIDTH
+        })
+    }
+    fn row_at(&self, x: f32, y: f32) -> Option<usize> {
+        let body = self.body_rect();
+        if !body.contains(Point2::new(x, y)) {
+            return None;
+        }
+        let n = ((y - body.y) / ITEM_HEIGHT) as usize;
+        if n < self.visible_count() && self.state.scroll + n < self.state.row_count {
+            Some(self.state.scroll + n)
+        } else {
+            None
+        }
+    }
+    fn visible_count(&self) -> usize {
+        (self.body_rect().h / ITEM_HEIGHT).floor().max(0.) as usize
+    }
+}
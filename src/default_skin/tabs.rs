@@ -0,0 +1,97 @@
+use crate::button::{ButtonMode, ButtonSkin, ButtonState};
+use crate::default_skin::button::DefaultButtonSkin;
+use crate::default_skin::image::draw_image_in_rect;
+use crate::default_skin::label::{draw_text_in_rect, TEXT_COLOR};
+use crate::image::ImageFit;
+use crate::label::{HorizontalAlign, TextOverflow, VerticalAlign};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 2.;
+const PADDING: f32 = 4.;
+
+fn header_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN;
+    rect
+}
+
+/// Draws radio buttons as tab headers: the title in a frame which is filled
+/// for the selected tab. Buttons in other modes are drawn by `DefaultButtonSkin`.
+#[derive(Default, Debug)]
+pub struct DefaultTabHeaderSkin {
+    state: ButtonState,
+    button_skin: DefaultButtonSkin,
+}
+
+impl DefaultTabHeaderSkin {
+    fn draw_header(&self, ctx: &mut Context, selected: bool) -> GameResult {
+        let rect = header_rect(self.state.rect);
+        let mesh = MeshBuilder::new()
+            .rectangle(
+                if selected {
+                    DrawMode::fill()
+                } else {
+                    DrawMode::stroke(1.)
+                },
+                rect,
+                graphics::WHITE,
+            )
+            .build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        let mut content = Rect::new(
+            rect.x + PADDING,
+            rect.y + PADDING,
+            rect.w - PADDING * 2.,
+            rect.h - PADDING * 2.,
+        );
+        if let Some(icon) = &self.state.icon {
+            let icon_rect = Rect::new(content.x, content.y, content.h, content.h);
+            draw_image_in_rect(ctx, icon, icon_rect, ImageFit::Contain)?;
+            content.x += content.h + PADDING;
+            content.w -= content.h + PADDING;
+        }
+        if self.state.label.is_empty() || content.w <= 0. {
+            return Ok(());
+        }
+        draw_text_in_rect(
+            ctx,
+            &self.state.label,
+            content,
+            HorizontalAlign::Center,
+            VerticalAlign::Center,
+            TextOverflow::Ellipsis,
+            if selected {
+                graphics::BLACK
+            } else {
+                TEXT_COLOR
+            },
+        )
+    }
+}
+
+impl EventHandlerProxy for DefaultTabHeaderSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        match self.state.mode {
+            ButtonMode::Radio(selected) => self.draw_header(ctx, selected),
+            _ => self.button_skin.draw(ctx),
+        }
+    }
+}
+
+impl ButtonSkin for DefaultTabHeaderSkin {
+    fn set_state(&mut self, state: &ButtonState) {
+        self.state = state.clone();
+        self.button_skin.set_state(state);
+    }
+    fn is_hot_area(&self, x: f32, y: f32) -> bool {
+        match self.state.mode {
+            ButtonMode::Radio(_) => header_rect(self.state.rect).contains(Point2::new(x, y)),
+            _ => self.button_skin.is_hot_area(x, y),
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use crate::default_skin::label::{make_text, TEXT_COLOR, TEXT_SCALE};
+use crate::default_skin::textinput::{caret_visible, char_offsets, nearest_offset};
+use crate::textarea::{TextAreaSkin, TextAreaState};
+use crate::textedit::char_slice;
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+const PADDING: f32 = 4.;
+const LINE_HEIGHT: f32 = TEXT_SCALE + 4.;
+const SCROLLBAR_WIDTH: f32 = 6.;
+const SELECTION_COLOR: Color = Color::new(0.3, 0.5, 1., 0.5);
+const SCROLLBAR_COLOR: Color = Color::new(1., 1., 1., 0.5);
+
+fn base_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN * 2.;
+    rect
+}
+
+fn text_rect(rect: Rect) -> Rect {
+    let mut rect = base_rect(rect);
+    rect.x += PADDING;
+    rect.y += PADDING;
+    rect.w -= PADDING * 2. + SCROLLBAR_WIDTH;
+    rect.h -= PADDING * 2.;
+    rect
+}
+
+/// Line of wrapped text. Offsets are horizontal positions of char boundaries
+/// from `start` to `end` inclusive. Wrapped line continues on the next line
+/// without newline char between them.
+#[derive(Clone, Debug)]
+struct VisualLine {
+    start: usize,
+    end: usize,
+    wrapped: bool,
+    offsets: Vec<f32>,
+}
+
+impl VisualLine {
+    /// Last position where caret is shown on this line
+    fn last(&self) -> usize {
+        if self.wrapped && self.end > self.start {
+            self.end - 1
+        } else {
+            self.end
+        }
+    }
+    fn index_at(&self, x: f32) -> usize {
+        (self.start + nearest_offset(&self.offsets, x)).min(self.last())
+    }
+}
+
+fn wrap_text(ctx: &Context, text: &str, width: f32) -> Vec<VisualLine> {
+    wrap_lines(text, width, |logical| char_offsets(ctx, logical))
+}
+
+/// Splits the text into lines no wider than `width` where possible, `measure`
+/// returns char boundary offsets of a line without newlines
+fn wrap_lines<F: Fn(&str) -> Vec<f32>>(text: &str, width: f32, measure: F) -> Vec<VisualLine> {
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    for logical in text.split('\n') {
+        let chars: Vec<char> = logical.chars().collect();
+        let offsets = measure(logical);
+        let mut push = |start: usize, end: usize, wrapped: bool| {
+            lines.push(VisualLine {
+                start: line_start + start,
+                end: line_start + end,
+                wrapped,
+                offsets: offsets[start..=end]
+                    .iter()
+                    .map(|o| o - offsets[start])
+                    .collect(),
+            })
+        };
+        let mut start = 0;
+        loop {
+            let mut end = start;
+            let mut last_break = None;
+            while end < chars.len() && offsets[end + 1] - offsets[start] <= width {
+                end += 1;
+                if chars[end - 1] == ' ' {
+                    last_break = Some(end)
+                }
+            }
+            if end == chars.len() {
+                push(start, end, false);
+                break;
+            }
+            let brk = if end == start {
+                start + 1
+            } else {
+                last_break.unwrap_or(end)
+            };
+            push(start, brk, true);
+            start = brk;
+        }
+        line_start += chars.len() + 1;
+    }
+    lines
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultTextAreaSkin {
+    state: TextAreaState,
+    lines: Vec<VisualLine>,
+    layout_width: Option<f32>,
+    scroll: usize,
+    follow_caret: bool,
+}
+
+impl DefaultTextAreaSkin {
+    fn update_layout(&mut self, ctx: &Context) {
+        let width = text_rect(self.state.rect).w;
+        if self.layout_width != Some(width) {
+            self.lines = wrap_text(ctx, &self.state.edit.text, width);
+            self.layout_width = Some(width);
+        }
+        let page = self.page_lines();
+        if self.follow_caret {
+            let line = self.line_of(self.state.edit.caret);
+            if line < self.scroll {
+                self.scroll = line
+            } else if line >= self.scroll + page {
+                self.scroll = line + 1 - page
+            }
+            self.follow_caret = false;
+        }
+        self.scroll = self.scroll.min(self.lines.len().saturating_sub(page));
+    }
+    fn line_of(&self, index: usize) -> usize {
+        self.lines
+            .iter()
+            .rposition(|l| l.start <= index)
+            .unwrap_or(0)
+    }
+    fn x_of(&self, index: usize) -> f32 {
+        let line = &self.lines[self.line_of(index)];
+        line.offsets[(index - line.start).min(line.offsets.len() - 1)]
+    }
+    fn index_by_lines(&self, index: usize, lines: isize) -> usize {
+        let x = self.x_of(index);
+        let n = self.line_of(index) as isize + lines;
+        let n = n.max(0).min(self.lines.len() as isize - 1) as usize;
+        self.lines[n].index_at(x)
+    }
+}
+
+impl EventHandlerProxy for DefaultTextAreaSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.update_layout(ctx);
+        let base = base_rect(self.state.rect);
+        let inner = text_rect(self.state.rect);
+        let page = self.page_lines();
+        let first = self.scroll;
+        let visible = first..(first + page).min(self.lines.len());
+        let line_y = |n: usize| inner.y + (n - first) as f32 * LINE_HEIGHT;
+
+        let mut mb = MeshBuilder::new();
+        mb.rectangle(
+            DrawMode::stroke(if self.state.focused { 2. } else { 1. }),
+            base,
+            graphics::WHITE,
+        );
+        if let Some((sel_start, sel_end)) = self.state.edit.selection() {
+            for n in visible.clone() {
+                let line = &self.lines[n];
+                let (start, end) = (sel_start.max(line.start), sel_end.min(line.end));
+                if start < end || (start == end && sel_start < start && sel_end > end) {
+                    let left = line.offsets[start - line.start];
+                    let right = line.offsets[end - line.start].min(inner.w);
+                    mb.rectangle(
+                        DrawMode::fill(),
+                        Rect::new(inner.x + left, line_y(n), right - left + 1., LINE_HEIGHT),
+                        SELECTION_COLOR,
+                    );
+                }
+            }
+        }
+        let caret_line = self.line_of(self.state.edit.caret);
+        if self.state.focused && visible.contains(&caret_line) && caret_visible(ctx) {
+            let x = inner.x + self.x_of(self.state.edit.caret);
+            mb.rectangle(
+                DrawMode::fill(),
+                Rect::new(x, line_y(caret_line), 1., LINE_HEIGHT),
+                graphics::WHITE,
+            );
+        }
+        if self.lines.len() > page {
+            let track = Rect::new(inner.right() + PADDING, inner.y, SCROLLBAR_WIDTH, inner.h);
+            let total = self.lines.len() as f32;
+            mb.rectangle(
+                DrawMode::fill(),
+                Rect::new(
+                    track.x,
+                    track.y + track.h * self.scroll as f32 / total,
+                    track.w,
+                    track.h * page as f32 / total,
+                ),
+                SCROLLBAR_COLOR,
+            );
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+
+        for n in visible {
+            let line = &self.lines[n];
+            let text = make_text(
+                char_slice(&self.state.edit.text, line.start, line.end),
+                TEXT_COLOR,
+            );
+            graphics::draw(
+                ctx,
+                &text,
+                DrawParam::default().dest(Point2::new(inner.x, line_y(n))),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TextAreaSkin for DefaultTextAreaSkin {
+    fn set_state(&mut self, state: &TextAreaState) {
+        if state.edit.text != self.state.edit.text {
+            self.layout_width = None;
+            self.follow_caret = true;
+        }
+        if state.edit.caret != self.state.edit.caret {
+            self.follow_caret = true;
+        }
+        self.state = state.clone();
+    }
+    fn is_hot_area(&self, x: f32, y: f32) -> bool {
+        base_rect(self.state.rect).contains(Point2::new(x, y))
+    }
+    fn char_index_at(&mut self, ctx: &mut Context, x: f32, y: f32) -> usize {
+        self.update_layout(ctx);
+        let inner = text_rect(self.state.rect);
+        let n = self.scroll as isize + ((y - inner.y) / LINE_HEIGHT).floor() as isize;
+        let n = n.max(0).min(self.lines.len() as isize - 1) as usize;
+        self.lines[n].index_at(x - inner.x)
+    }
+    fn char_index_by_lines(&mut self, ctx: &mut Context, index: usize, lines: isize) -> usize {
+        self.update_layout(ctx);
+        self.index_by_lines(index, lines)
+    }
+    fn line_bounds(&mut self, ctx: &mut Context, index: usize) -> (usize, usize) {
+        self.update_layout(ctx);
+        let line = &self.lines[self.line_of(index)];
+        (line.start, line.last())
+    }
+    fn page_lines(&self) -> usize {
+        ((text_rect(self.state.rect).h / LINE_HEIGHT).floor() as usize).max(1)
+    }
+    fn scroll_by(&mut self, lines: isize) {
+        let max_scroll = self.lines.len().saturating_sub(self.page_lines());
+        let scroll = (self.scroll as isize + lines).max(0) as usize;
+        self.scroll = scroll.min(max_scroll);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every char is 10 wide
+    fn skin(text: &str, width: f32) -> DefaultTextAreaSkin {
+        let lines = wrap_lines(text, width, |s| {
+            (0..=s.chars().count()).map(|n| n as f32 * 10.).collect()
+        });
+        DefaultTextAreaSkin {
+            lines,
+            ..DefaultTextAreaSkin::default()
+        }
+    }
+
+    #[test]
+    fn text_is_wrapped_at_spaces_and_newlines() {
+        let skin = skin("aaaa bbbb cc\ndd", 60.);
+        let bounds: Vec<_> = skin
+            .lines
+            .iter()
+            .map(|l| (l.start, l.end, l.wrapped))
+            .collect();
+        assert_eq!(
+            bounds,
+            vec![
+                (0, 5, true),
+                (5, 10, true),
+                (10, 12, false),
+                (13, 15, false)
+            ]
+        );
+        // caret doesn't stop after the space where the line is wrapped
+        assert_eq!(skin.lines[0].last(), 4);
+        assert_eq!(skin.line_of(5), 1);
+        // word longer than the line is broken anywhere
+        let skin = self::skin("abcdefgh", 30.);
+        assert_eq!(skin.lines.len(), 3);
+        assert_eq!((skin.lines[1].start, skin.lines[1].end), (3, 6));
+    }
+
+    #[test]
+    fn caret_keeps_column_across_wrapped_lines() {
+        let skin = skin("aaaa bbbb cc\ndd", 60.);
+        assert_eq!(skin.index_by_lines(2, 1), 7);
+        assert_eq!(skin.index_by_lines(7, 1), 12);
+        assert_eq!(skin.index_by_lines(12, 1), 15);
+        assert_eq!(skin.index_by_lines(15, -2), 7);
+        // column beyond the shorter line goes to its last position
+        assert_eq!(skin.index_by_lines(4, 1), 9);
+        assert_eq!(skin.index_by_lines(9, 1), 12);
+        // moving past the first or last line stays on it
+        assert_eq!(skin.index_by_lines(3, -1), 3);
+        assert_eq!(skin.index_by_lines(14, 5), 14);
+    }
+}
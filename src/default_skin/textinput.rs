@@ -0,0 +1,178 @@
+use crate::default_skin::label::{make_text, TEXT_COLOR};
+use crate::textedit::char_slice;
+use crate::textinput::{TextInputSkin, TextInputState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{timer, Context, GameResult};
+use std::collections::HashMap;
+
+const MARGIN: f32 = 5.;
+const PADDING: f32 = 4.;
+const PLACEHOLDER_COLOR: Color = Color::new(0.6, 0.6, 0.6, 1.);
+const SELECTION_COLOR: Color = Color::new(0.3, 0.5, 1., 0.5);
+const CARET_BLINK_MS: u128 = 500;
+
+fn base_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN * 2.;
+    rect
+}
+
+fn text_rect(rect: Rect) -> Rect {
+    let mut rect = base_rect(rect);
+    rect.x += PADDING;
+    rect.y += PADDING;
+    rect.w -= PADDING * 2.;
+    rect.h -= PADDING * 2.;
+    rect
+}
+
+/// Width of the string including trailing spaces which are ignored by `Text::width`
+pub(crate) fn prefix_width(ctx: &Context, s: &str) -> f32 {
+    if s.is_empty() {
+        return 0.;
+    }
+    let sentinel = make_text("|", TEXT_COLOR).width(ctx) as f32;
+    make_text(&(s.to_string() + "|"), TEXT_COLOR).width(ctx) as f32 - sentinel
+}
+
+/// Horizontal offsets of all char boundaries in the string. Char advances are
+/// measured once per distinct char and accumulated, so kerning is ignored.
+pub(crate) fn char_offsets(ctx: &Context, s: &str) -> Vec<f32> {
+    let mut advances = HashMap::new();
+    let mut offsets = Vec::with_capacity(s.len() + 1);
+    let mut x = 0.;
+    offsets.push(x);
+    for c in s.chars() {
+        x += *advances
+            .entry(c)
+            .or_insert_with(|| prefix_width(ctx, c.encode_utf8(&mut [0; 4])));
+        offsets.push(x);
+    }
+    offsets
+}
+
+pub(crate) fn nearest_offset(offsets: &[f32], x: f32) -> usize {
+    let mut best = 0;
+    for (n, offset) in offsets.iter().enumerate() {
+        if (offset - x).abs() < (offsets[best] - x).abs() {
+            best = n
+        }
+    }
+    best
+}
+
+pub(crate) fn caret_visible(ctx: &Context) -> bool {
+    (timer::time_since_start(ctx).as_millis() / CARET_BLINK_MS).is_multiple_of(2)
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultTextInputSkin {
+    state: TextInputState,
+    offsets: Vec<f32>,
+    scroll: f32,
+}
+
+impl DefaultTextInputSkin {
+    fn update_layout(&mut self, ctx: &Context) {
+        let text = &self.state.edit.text;
+        if self.offsets.len() != text.chars().count() + 1 {
+            self.offsets = char_offsets(ctx, text);
+        }
+        let width = text_rect(self.state.rect).w.max(0.);
+        let caret_x = self.offsets[self.state.edit.caret.min(self.offsets.len() - 1)];
+        if caret_x < self.scroll {
+            self.scroll = caret_x
+        } else if caret_x > self.scroll + width {
+            self.scroll = caret_x - width
+        }
+        let max_scroll = (self.offsets[self.offsets.len() - 1] - width).max(0.);
+        self.scroll = self.scroll.min(max_scroll).max(0.);
+    }
+}
+
+impl EventHandlerProxy for DefaultTextInputSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.update_layout(ctx);
+        let base = base_rect(self.state.rect);
+        let inner = text_rect(self.state.rect);
+        let x_of = |offset: f32| inner.x + offset - self.scroll;
+        let mut mb = MeshBuilder::new();
+        mb.rectangle(
+            DrawMode::stroke(if self.state.focused { 2. } else { 1. }),
+            base,
+            graphics::WHITE,
+        );
+        if let Some((start, end)) = self.state.edit.selection() {
+            let left = x_of(self.offsets[start]).max(inner.x);
+            let right = x_of(self.offsets[end]).min(inner.right());
+            if right > left {
+                mb.rectangle(
+                    DrawMode::fill(),
+                    Rect::new(left, inner.y, right - left, inner.h),
+                    SELECTION_COLOR,
+                );
+            }
+        }
+        if self.state.focused && caret_visible(ctx) {
+            let x = x_of(self.offsets[self.state.edit.caret]);
+            mb.rectangle(
+                DrawMode::fill(),
+                Rect::new(x, inner.y, 1., inner.h),
+                graphics::WHITE,
+            );
+        }
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+
+        let text = &self.state.edit.text;
+        let (visible, dx, color) = if text.is_empty() {
+            (self.state.placeholder.clone(), 0., PLACEHOLDER_COLOR)
+        } else {
+            // only whole chars fitting into the box are drawn
+            let first = self
+                .offsets
+                .iter()
+                .position(|o| *o >= self.scroll)
+                .unwrap_or(0);
+            let last = self
+                .offsets
+                .iter()
+                .rposition(|o| *o <= self.scroll + inner.w)
+                .unwrap_or(first)
+                .max(first);
+            (
+                char_slice(text, first, last).to_string(),
+                self.offsets[first] - self.scroll,
+                TEXT_COLOR,
+            )
+        };
+        let text = make_text(&visible, color);
+        let dy = (inner.h - text.height(ctx) as f32) / 2.;
+        graphics::draw(
+            ctx,
+            &text,
+            DrawParam::default().dest(Point2::new(inner.x + dx, inner.y + dy)),
+        )
+    }
+}
+
+impl TextInputSkin for DefaultTextInputSkin {
+    fn set_state(&mut self, state: &TextInputState) {
+        if state.edit.text != self.state.edit.text {
+            self.offsets.clear();
+        }
+        self.state = state.clone();
+    }
+    fn is_hot_area(&self, x: f32, y: f32) -> bool {
+        base_rect(self.state.rect).contains(Point2::new(x, y))
+    }
+    fn char_index_at(&mut self, ctx: &mut Context, x: f32, _y: f32) -> usize {
+        self.update_layout(ctx);
+        let inner = text_rect(self.state.rect);
+        nearest_offset(&self.offsets, x - inner.x + self.scroll)
+    }
+}
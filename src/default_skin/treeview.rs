@@ -0,0 +1,155 @@
+use crate::default_skin::label::{draw_text_in_rect, TEXT_COLOR, TEXT_SCALE};
+use crate::label::{HorizontalAlign, TextOverflow, VerticalAlign};
+use crate::treeview::{TreeViewSkin, TreeViewState};
+use crate::EventHandlerProxy;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const MARGIN: f32 = 5.;
+const ITEM_HEIGHT: f32 = TEXT_SCALE + 8.;
+const INDENT: f32 = 16.;
+const ARROW_SIZE: f32 = 8.;
+const SCROLLBAR_WIDTH: f32 = 4.;
+const SELECTION_COLOR: Color = Color::new(0.3, 0.5, 1., 1.);
+const SCROLLBAR_COLOR: Color = Color::new(0.5, 0.5, 0.5, 1.);
+
+fn base_rect(mut rect: Rect) -> Rect {
+    rect.x += MARGIN;
+    rect.y += MARGIN;
+    rect.w -= MARGIN * 2.;
+    rect.h -= MARGIN * 2.;
+    rect
+}
+
+#[derive(Default, Debug)]
+pub struct DefaultTreeViewSkin {
+    state: TreeViewState,
+}
+
+impl DefaultTreeViewSkin {
+    fn row_rect(&self, index: usize) -> Rect {
+        let rect = base_rect(self.state.rect);
+        let row = index as f32 - self.state.scroll as f32;
+        Rect::new(
+            rect.x,
+            rect.y + row * ITEM_HEIGHT,
+            rect.w - SCROLLBAR_WIDTH,
+            ITEM_HEIGHT,
+        )
+    }
+    /// Square of the row's expand/collapse arrow, the label follows it
+    fn arrow_rect(&self, index: usize) -> Rect {
+        let rect = self.row_rect(index);
+        let x = rect.x + self.state.rows[index].depth as f32 * INDENT;
+        Rect::new(x, rect.y, INDENT, rect.h)
+    }
+    fn visible_range(&self) -> std::ops::Range<usize> {
+        let end = (self.state.scroll + self.visible_count()).min(self.state.rows.len());
+        self.state.scroll.min(end)..end
+    }
+}
+
+impl EventHandlerProxy for DefaultTreeViewSkin {
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let rect = base_rect(self.state.rect);
+        let mut mb = MeshBuilder::new();
+        for index in self.visible_range() {
+            let row = &self.state.rows[index];
+            if row.selected {
+                mb.rectangle(DrawMode::fill(), self.row_rect(index), SELECTION_COLOR);
+                if self.state.focused {
+                    mb.rectangle(DrawMode::stroke(1.), self.row_rect(index), graphics::WHITE);
+                }
+            }
+            if row.expandable {
+                let arrow = self.arrow_rect(index);
+                let (cx, cy) = (arrow.x + arrow.w / 2., arrow.y + arrow.h / 2.);
+                let d = ARROW_SIZE / 2.;
+                let points = if row.expanded {
+                    [
+                        Point2::new(cx - d, cy - d / 2.),
+                        Point2::new(cx + d, cy - d / 2.),
+                        Point2::new(cx, cy + d / 2.),
+                    ]
+                } else {
+                    [
+                        Point2::new(cx - d / 2., cy - d),
+                        Point2::new(cx + d / 2., cy),
+                        Point2::new(cx - d / 2., cy + d),
+                    ]
+                };
+                mb.polygon(DrawMode::fill(), &points, TEXT_COLOR)?;
+            }
+        }
+        let count = self.state.rows.len();
+        let visible = self.visible_count();
+        if count > visible {
+            let h = rect.h * visible as f32 / count as f32;
+            let y = rect.y + rect.h * self.state.scroll as f32 / count as f32;
+            mb.rectangle(
+                DrawMode::fill(),
+                Rect::new(rect.right() - SCROLLBAR_WIDTH, y, SCROLLBAR_WIDTH, h),
+                SCROLLBAR_COLOR,
+            );
+        }
+        mb.rectangle(
+            DrawMode::stroke(if self.state.focused { 2. } else { 1. }),
+            rect,
+            graphics::WHITE,
+        );
+        let mesh = mb.build(ctx)?;
+        graphics::draw(ctx, &mesh, DrawParam::default())?;
+        for index in self.visible_range() {
+            let row = &self.state.rows[index];
+            let arrow = self.arrow_rect(index);
+            let mut label_rect = self.row_rect(index);
+            label_rect.w = label_rect.right() - arrow.right() - MARGIN;
+            label_rect.x = arrow.right();
+            let label = if row.loading {
+                format!("{} \u{2026}", row.label)
+            } else {
+                row.label.clone()
+            };
+            draw_text_in_rect(
+                ctx,
+                &label,
+                label_rect,
+                HorizontalAlign::Left,
+                VerticalAlign::Center,
+                TextOverflow::Ellipsis,
+                TEXT_COLOR,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TreeViewSkin for DefaultTreeViewSkin {
+    fn set_state(&mut self, state: &TreeViewState) {
+        self.state = state.clone();
+    }
+    fn is_hot_area(&self, x: f32, y: f32) -> bool {
+        base_rect(self.state.rect).contains(Point2::new(x, y))
+    }
+    fn row_at(&self, x: f32, y: f32) -> Option<usize> {
+        let rect = base_rect(self.state.rect);
+        if !rect.contains(Point2::new(x, y)) {
+            return None;
+        }
+        let index = self.state.scroll + ((y - rect.y) / ITEM_HEIGHT) as usize;
+        if self.visible_range().contains(&index) {
+            Some(index)
+        } else {
+            None
+        }
+    }
+    fn is_arrow_at(&self, row: usize, x: f32, y: f32) -> bool {
+        row < self.state.rows.len()
+            && self.state.rows[row].expandable
+            && self.arrow_rect(row).contains(Point2::new(x, y))
+    }
+    fn visible_count(&self) -> usize {
+        (base_rect(self.state.rect).h / ITEM_HEIGHT).floor().max(0.) as usize
+    }
+}
@@ -0,0 +1,419 @@
+use crate::button::{Button, ButtonSkin};
+use crate::overlay::{OverlayId, Placement, PopupOptions};
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::{Context, GameResult};
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+
+const DIALOG_WIDTH: f32 = 400.;
+const DIALOG_HEIGHT: f32 = 200.;
+
+struct AnswerState<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    completed: bool,
+}
+
+/// Future resolved once by whoever answers first
+struct Answer<T>(Arc<Mutex<AnswerState<T>>>);
+
+impl<T> Clone for Answer<T> {
+    fn clone(&self) -> Self {
+        Answer(self.0.clone())
+    }
+}
+
+impl<T> Answer<T> {
+    fn new() -> Self {
+        Answer(Arc::new(Mutex::new(AnswerState {
+            value: None,
+            waker: None,
+            completed: false,
+        })))
+    }
+    fn complete(&self, value: T) {
+        let mut state = self.0.lock().unwrap();
+        if !state.completed {
+            state.completed = true;
+            state.value = Some(value);
+            if let Some(waker) = state.waker.take() {
+                waker.wake()
+            }
+        }
+    }
+}
+
+impl<T> Future for Answer<T> {
+    type Output = T;
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<T> {
+        let mut state = self.0.lock().unwrap();
+        match state.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct DialogState {
+    pub title: String,
+    pub text: String,
+    pub has_content: bool,
+    pub rect: Rect,
+}
+
+pub trait DialogSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &DialogState);
+    /// Area for custom content, text is drawn there if there is no content
+    fn content_rect(&self) -> Rect;
+    /// Area for the row of buttons
+    fn buttons_rect(&self) -> Rect;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DialogId(SrvId);
+
+impl From<DialogId> for SrvId {
+    fn from(v: DialogId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum DialogOp {
+    SetTitle(String),
+    SetText(String),
+    Finish(Option<usize>),
+}
+
+impl DialogId {
+    pub async fn set_title<T: Into<String>>(self, title: T) {
+        send_request(self.0, DialogOp::SetTitle(title.into()))
+            .await
+            .unwrap()
+    }
+    pub async fn set_text<T: Into<String>>(self, text: T) {
+        send_request(self.0, DialogOp::SetText(text.into()))
+            .await
+            .unwrap()
+    }
+    /// Answers the dialog as if the button with given index was pressed,
+    /// `None` cancels it. Only the first answer counts.
+    pub async fn finish(self, answer: Option<usize>) {
+        send_request(self.0, DialogOp::Finish(answer))
+            .await
+            .unwrap()
+    }
+}
+
+/// Modal window in the overlay with optional custom content and a row of
+/// buttons. Result is the index of the pressed button or `None` if the
+/// dialog was cancelled with Escape or closed otherwise.
+pub struct Dialog<S: DialogSkin, B: ButtonSkin> {
+    state: DialogState,
+    skin: S,
+    reg: ServiceRegistration,
+    content: Option<Box<dyn Widget>>,
+    buttons: Vec<Button<B>>,
+    answer: Answer<Option<usize>>,
+}
+
+impl<S: DialogSkin, B: ButtonSkin + 'static> Dialog<S, B> {
+    fn new() -> Self {
+        Self {
+            state: DialogState {
+                rect: Rect::new(0., 0., DIALOG_WIDTH, DIALOG_HEIGHT),
+                ..DialogState::default()
+            },
+            skin: S::default(),
+            reg: register_service(),
+            content: None,
+            buttons: Vec::new(),
+            answer: Answer::new(),
+        }
+    }
+    pub fn id(&self) -> DialogId {
+        DialogId(self.reg.id())
+    }
+    pub fn set_title(&mut self, title: String) {
+        self.state.title = title
+    }
+    pub fn set_text(&mut self, text: String) {
+        self.state.text = text
+    }
+    /// The dialog stays open, it's closed by `DialogBuilder::run` which waits
+    /// for the answer
+    pub fn finish(&mut self, answer: Option<usize>) {
+        self.answer.complete(answer)
+    }
+    fn update_widgets_rects(&mut self) {
+        self.skin.set_state(&self.state);
+        if let Some(content) = &mut self.content {
+            content.set_rect(self.skin.content_rect());
+        }
+        let rect = self.skin.buttons_rect();
+        let dw = rect.w / self.buttons.len().max(1) as f32;
+        let mut x = rect.x;
+        for button in &mut self.buttons {
+            button.set_rect(Rect::new(x, rect.y, dw, rect.h));
+            x += dw;
+        }
+    }
+    fn children_iter_mut(&mut self) -> impl Iterator<Item = &mut dyn Widget> {
+        let content = self
+            .content
+            .iter_mut()
+            .map(|w| w.as_mut() as &mut dyn Widget);
+        let buttons = self.buttons.iter_mut().map(|b| b as &mut dyn Widget);
+        content.chain(buttons)
+    }
+}
+
+impl<S: DialogSkin + 'static, B: ButtonSkin + 'static> Widget for Dialog<S, B> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+        self.update_widgets_rects();
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn children(&self) -> Vec<&dyn Widget> {
+        let content = self.content.iter().map(|w| w.as_ref() as &dyn Widget);
+        let buttons = self.buttons.iter().map(|b| b as &dyn Widget);
+        content.chain(buttons).collect()
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.children_iter_mut().collect()
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![("title", self.state.title.clone())]
+    }
+}
+
+impl<S: DialogSkin + 'static, B: ButtonSkin + 'static> EventHandlerProxy for Dialog<S, B> {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            DialogOp::SetTitle(title) => {
+                self.set_title(title);
+                Some(Box::new(()))
+            }
+            DialogOp::SetText(text) => {
+                self.set_text(text);
+                Some(Box::new(()))
+            }
+            DialogOp::Finish(answer) => {
+                self.finish(answer);
+                Some(Box::new(()))
+            }
+        });
+        for w in self.children_iter_mut() {
+            w.update(ctx)?
+        }
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)?;
+        for w in self.children_iter_mut() {
+            w.draw(ctx)?
+        }
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        for w in self.children_iter_mut() {
+            consumed |= w.mouse_button_down_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        for w in self.children_iter_mut() {
+            consumed |= w.mouse_button_up_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        let mut consumed = false;
+        for w in self.children_iter_mut() {
+            consumed |= w.mouse_motion_event(ctx, x, y, dx, dy)
+        }
+        consumed
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        let mut consumed = false;
+        for w in self.children_iter_mut() {
+            consumed |= w.mouse_wheel_event(ctx, x, y)
+        }
+        consumed
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        let mut consumed = false;
+        for w in self.children_iter_mut() {
+            consumed |= w.key_down_event(ctx, keycode, keymods, repeat)
+        }
+        if !consumed {
+            match keycode {
+                KeyCode::Escape => self.finish(None),
+                KeyCode::Return | KeyCode::NumpadEnter if !self.buttons.is_empty() => {
+                    self.finish(Some(0))
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        let mut consumed = false;
+        for w in self.children_iter_mut() {
+            consumed |= w.key_up_event(ctx, keycode, keymods)
+        }
+        consumed
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        let mut consumed = false;
+        for w in self.children_iter_mut() {
+            consumed |= w.text_input_event(ctx, character)
+        }
+        consumed
+    }
+}
+
+pub struct DialogBuilder<S: DialogSkin, B: ButtonSkin> {
+    dialog: Dialog<S, B>,
+}
+
+impl<S: DialogSkin + 'static, B: ButtonSkin + 'static> Default for DialogBuilder<S, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: DialogSkin + 'static, B: ButtonSkin + 'static> DialogBuilder<S, B> {
+    pub fn new() -> Self {
+        Self {
+            dialog: Dialog::new(),
+        }
+    }
+    pub fn set_title<T: Into<String>>(mut self, title: T) -> Self {
+        self.dialog.set_title(title.into());
+        self
+    }
+    pub fn set_text<T: Into<String>>(mut self, text: T) -> Self {
+        self.dialog.set_text(text.into());
+        self
+    }
+    pub fn set_size(mut self, w: f32, h: f32) -> Self {
+        self.dialog.state.rect.w = w;
+        self.dialog.state.rect.h = h;
+        self
+    }
+    pub fn set_content(mut self, content: impl Widget + 'static) -> Self {
+        self.dialog.content = Some(Box::new(content));
+        self.dialog.state.has_content = true;
+        self
+    }
+    /// First button is the default one chosen by Enter
+    pub fn add_button<T: Into<String>>(mut self, label: T) -> Self {
+        let mut button = Button::new();
+        button.set_label(label.into());
+        self.dialog.buttons.push(button);
+        self
+    }
+    /// Opens the dialog in the overlay, waits for the answer and closes the
+    /// dialog. Resolves to `None` if the dialog was cancelled or closed by
+    /// someone else.
+    pub async fn run(self, overlay: OverlayId) -> Option<usize> {
+        let mut dialog = self.dialog;
+        let answer = dialog.answer.clone();
+        let id = dialog.srv_id();
+        for (n, button) in dialog.buttons.iter_mut().enumerate() {
+            let answer = answer.clone();
+            button.on_click(move |_| answer.complete(Some(n)));
+        }
+        dialog.update_widgets_rects();
+        let options = PopupOptions::new()
+            .set_placement(Placement::Center)
+            .set_modal(true)
+            .on_close({
+                let answer = answer.clone();
+                move |_| answer.complete(None)
+            });
+        overlay.open_with(dialog, options).await;
+        let result = answer.await;
+        overlay.close(id).await;
+        result
+    }
+}
+
+/// Shows the text with OK button and waits until it's dismissed
+pub async fn message<S, B, T>(overlay: OverlayId, text: T)
+where
+    S: DialogSkin + 'static,
+    B: ButtonSkin + 'static,
+    T: Into<String>,
+{
+    DialogBuilder::<S, B>::new()
+        .set_title("Message")
+        .set_text(text)
+        .add_button("OK")
+        .run(overlay)
+        .await;
+}
+
+/// Asks Yes/No question, cancelling the dialog means No
+pub async fn confirm<S, B, T>(overlay: OverlayId, text: T) -> bool
+where
+    S: DialogSkin + 'static,
+    B: ButtonSkin + 'static,
+    T: Into<String>,
+{
+    let answer = DialogBuilder::<S, B>::new()
+        .set_title("Confirm")
+        .set_text(text)
+        .add_button("Yes")
+        .add_button("No")
+        .run(overlay)
+        .await;
+    answer == Some(0)
+}
@@ -0,0 +1,326 @@
+use crate::inspect::Inspector;
+use crate::overlay::{Overlay, OverlayId};
+use crate::tooltip::Tooltips;
+use crate::{EventHandlerProxy, Widget};
+use ggez::event::{self, EventHandler, MouseButton};
+use ggez::graphics::{self, Canvas, Color, Rect};
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::input::mouse;
+use ggez::{Context, GameResult};
+use std::time::Duration;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GuiEvent {
+    MouseButtonDown {
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    },
+    MouseButtonUp {
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    },
+    MouseMotion {
+        x: f32,
+        y: f32,
+        dx: f32,
+        dy: f32,
+    },
+    MouseWheel {
+        x: f32,
+        y: f32,
+    },
+    KeyDown {
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    },
+    KeyUp {
+        keycode: KeyCode,
+        keymods: KeyMods,
+    },
+    TextInput(char),
+}
+
+type UnconsumedEventHandler = Box<dyn FnMut(&mut Context, GuiEvent)>;
+
+/// Owns the root widget and runs it as ggez `EventHandler`. To embed the GUI
+/// into existing game loop call `handle_event`, `update_gui` and `draw_gui` instead.
+pub struct GuiRoot {
+    root: Box<dyn Widget>,
+    overlay: Overlay,
+    rect: Option<Rect>,
+    background: Color,
+    inspector: Inspector,
+    inspector_key: Option<KeyCode>,
+    tooltips: Tooltips,
+    on_unconsumed_event: Option<UnconsumedEventHandler>,
+}
+
+impl GuiRoot {
+    pub fn new(root: impl Widget + 'static) -> Self {
+        Self::new_box(Box::new(root))
+    }
+    pub fn new_box(mut root: Box<dyn Widget>) -> Self {
+        let overlay = Overlay::new();
+        root.attach_overlay(overlay.id());
+        Self {
+            root,
+            overlay,
+            rect: None,
+            background: Color::new(0., 0., 0., 0.),
+            inspector: Inspector::new(),
+            inspector_key: None,
+            tooltips: Tooltips::new(),
+            on_unconsumed_event: None,
+        }
+    }
+    pub fn root(&self) -> &dyn Widget {
+        self.root.as_ref()
+    }
+    pub fn mut_root(&mut self) -> &mut dyn Widget {
+        self.root.as_mut()
+    }
+    /// Replaces the root widget and returns the previous one
+    pub fn set_root_box(&mut self, mut root: Box<dyn Widget>) -> Box<dyn Widget> {
+        root.attach_overlay(self.overlay.id());
+        if let Some(rect) = self.rect {
+            root.set_rect(rect);
+        }
+        std::mem::replace(&mut self.root, root)
+    }
+    pub fn set_root(&mut self, root: impl Widget + 'static) -> Box<dyn Widget> {
+        self.set_root_box(Box::new(root))
+    }
+    pub fn overlay(&self) -> OverlayId {
+        self.overlay.id()
+    }
+    pub fn set_background(&mut self, color: Color) {
+        self.background = color
+    }
+    pub fn get_background(&self) -> Color {
+        self.background
+    }
+    pub fn inspector(&mut self) -> &mut Inspector {
+        &mut self.inspector
+    }
+    /// Key toggling the inspector when pressed without modifiers
+    pub fn set_inspector_key(&mut self, key: Option<KeyCode>) {
+        self.inspector_key = key
+    }
+    pub fn tooltips(&mut self) -> &mut Tooltips {
+        &mut self.tooltips
+    }
+    /// Handler for events which were not consumed by any widget. Without it
+    /// unconsumed Escape quits the application like default ggez handler does.
+    pub fn on_unconsumed_event<F: FnMut(&mut Context, GuiEvent) + 'static>(&mut self, f: F) {
+        self.on_unconsumed_event = Some(Box::new(f))
+    }
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = Some(rect);
+        self.root.set_rect(rect);
+        self.overlay.set_rect(rect);
+    }
+    pub fn get_rect(&self) -> Option<Rect> {
+        self.rect
+    }
+
+    /// Passes the event to popups and then to widgets and returns `true`
+    /// if some of them consumed it. Unconsumed event handler is not called,
+    /// the caller is responsible for it.
+    pub fn handle_event(&mut self, ctx: &mut Context, event: GuiEvent) -> bool {
+        if let GuiEvent::MouseButtonDown { .. } = event {
+            self.tooltips.hide();
+        }
+        if let GuiEvent::KeyDown {
+            keycode,
+            keymods,
+            repeat,
+        } = event
+        {
+            if Some(keycode) == self.inspector_key && !repeat && keymods.is_empty() {
+                self.inspector.toggle();
+                return true;
+            }
+            if !repeat && self.dispatch_shortcut(ctx, keycode, keymods) {
+                return true;
+            }
+        }
+        Self::dispatch(&mut self.overlay, ctx, event)
+            || Self::dispatch(self.root.as_mut(), ctx, event)
+    }
+
+    /// Modal popup blocks shortcuts of the widgets below it
+    fn dispatch_shortcut(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        let overlay: &mut dyn Widget = &mut self.overlay;
+        overlay.dispatch_shortcut(ctx, keycode, keymods)
+            || (!self.overlay.is_modal() && self.root.dispatch_shortcut(ctx, keycode, keymods))
+    }
+
+    fn dispatch(widget: &mut dyn Widget, ctx: &mut Context, event: GuiEvent) -> bool {
+        match event {
+            GuiEvent::MouseButtonDown { button, x, y } => {
+                widget.mouse_button_down_event(ctx, button, x, y)
+            }
+            GuiEvent::MouseButtonUp { button, x, y } => {
+                widget.mouse_button_up_event(ctx, button, x, y)
+            }
+            GuiEvent::MouseMotion { x, y, dx, dy } => widget.mouse_motion_event(ctx, x, y, dx, dy),
+            GuiEvent::MouseWheel { x, y } => widget.mouse_wheel_event(ctx, x, y),
+            GuiEvent::KeyDown {
+                keycode,
+                keymods,
+                repeat,
+            } => widget.key_down_event(ctx, keycode, keymods, repeat),
+            GuiEvent::KeyUp { keycode, keymods } => widget.key_up_event(ctx, keycode, keymods),
+            GuiEvent::TextInput(character) => widget.text_input_event(ctx, character),
+        }
+    }
+
+    /// Serves widget requests. May be called with different rate than `draw_gui`
+    pub fn update_gui(&mut self, ctx: &mut Context) -> GameResult {
+        if self.rect.is_none() {
+            self.set_rect(graphics::screen_coordinates(ctx));
+        }
+        self.root.update(ctx)?;
+        self.overlay.update(ctx)?;
+        self.update_tooltips(ctx);
+        Ok(())
+    }
+
+    /// Popup under the mouse or modal popup hides tooltips of the widgets below
+    fn update_tooltips(&mut self, ctx: &mut Context) {
+        let mouse = mouse::position(ctx);
+        let overlay: &dyn Widget = &self.overlay;
+        let layer = match overlay.widget_at(mouse.x, mouse.y) {
+            Some(w) if w.srv_id() != overlay.srv_id() => overlay,
+            _ if self.overlay.is_modal() => overlay,
+            _ => self.root.as_ref(),
+        };
+        self.tooltips.update(ctx, layer);
+    }
+
+    /// Draws widgets into the rect set by `set_rect` (whole screen by default)
+    /// without clearing and presenting the frame
+    pub fn draw_gui(&mut self, ctx: &mut Context) -> GameResult {
+        self.root.draw(ctx)?;
+        self.overlay.draw(ctx)?;
+        if let Some(rect) = self.rect {
+            self.tooltips.draw(ctx, rect)?;
+        }
+        self.inspector.draw(ctx, self.root.as_ref())
+    }
+
+    /// Draws widgets onto the canvas and switches rendering back to the screen
+    pub fn draw_gui_to_canvas(&mut self, ctx: &mut Context, canvas: &Canvas) -> GameResult {
+        graphics::set_canvas(ctx, Some(canvas));
+        let result = self.draw_gui(ctx);
+        graphics::set_canvas(ctx, None);
+        result
+    }
+
+    fn process_event(&mut self, ctx: &mut Context, event: GuiEvent) {
+        if !self.handle_event(ctx, event) {
+            if let Some(handler) = &mut self.on_unconsumed_event {
+                handler(ctx, event)
+            } else if let GuiEvent::KeyDown {
+                keycode: KeyCode::Escape,
+                ..
+            } = event
+            {
+                event::quit(ctx)
+            }
+        }
+    }
+}
+
+impl EventHandler for GuiRoot {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        self.update_gui(ctx)
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        graphics::clear(ctx, self.background);
+        self.draw_gui(ctx)?;
+        graphics::present(ctx)
+    }
+
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+        self.process_event(ctx, GuiEvent::MouseButtonDown { button, x, y })
+    }
+
+    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+        self.process_event(ctx, GuiEvent::MouseButtonUp { button, x, y })
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) {
+        self.process_event(ctx, GuiEvent::MouseMotion { x, y, dx, dy })
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) {
+        self.process_event(ctx, GuiEvent::MouseWheel { x, y })
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) {
+        self.process_event(
+            ctx,
+            GuiEvent::KeyDown {
+                keycode,
+                keymods,
+                repeat,
+            },
+        )
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) {
+        self.process_event(ctx, GuiEvent::KeyUp { keycode, keymods })
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) {
+        self.process_event(ctx, GuiEvent::TextInput(character))
+    }
+
+    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) {
+        let new_rect = Rect::new(0., 0., width, height);
+        self.set_rect(new_rect);
+        graphics::set_screen_coordinates(ctx, new_rect).unwrap();
+    }
+}
+
+pub struct GuiRootBuilder {
+    gui_root: GuiRoot,
+}
+
+impl GuiRootBuilder {
+    pub fn new(root: impl Widget + 'static) -> Self {
+        Self {
+            gui_root: GuiRoot::new(root),
+        }
+    }
+    pub fn set_background(mut self, color: Color) -> Self {
+        self.gui_root.set_background(color);
+        self
+    }
+    pub fn set_inspector_key(mut self, key: KeyCode) -> Self {
+        self.gui_root.set_inspector_key(Some(key));
+        self
+    }
+    pub fn set_tooltip_delay(mut self, delay: Duration) -> Self {
+        self.gui_root.tooltips().set_delay(delay);
+        self
+    }
+    pub fn on_unconsumed_event<F: FnMut(&mut Context, GuiEvent) + 'static>(mut self, f: F) -> Self {
+        self.gui_root.on_unconsumed_event(f);
+        self
+    }
+    pub fn build(self) -> GuiRoot {
+        self.gui_root
+    }
+}
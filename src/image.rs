@@ -0,0 +1,168 @@
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::graphics::{self, Rect};
+use ggez::{Context, GameResult};
+use std::fmt::Debug;
+
+/// How the image is fitted into the widget's rect
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum ImageFit {
+    /// Fills the rect ignoring aspect ratio
+    Stretch,
+    /// Largest size fitting into the rect keeping aspect ratio
+    #[default]
+    Contain,
+    /// Smallest size covering the rect keeping aspect ratio, the rest is cropped
+    Cover,
+    /// Natural size in the middle of the rect, cropped if larger
+    Center,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ImageState {
+    pub image: Option<graphics::Image>,
+    pub fit: ImageFit,
+    pub rect: Rect,
+}
+
+pub trait ImageSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &ImageState);
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ImageId(SrvId);
+
+impl From<ImageId> for SrvId {
+    fn from(v: ImageId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum ImageOp {
+    SetImage(Option<graphics::Image>),
+    GetFit,
+    SetFit(ImageFit),
+}
+
+impl ImageId {
+    pub async fn set_image(self, image: Option<graphics::Image>) {
+        send_request(self.0, ImageOp::SetImage(image))
+            .await
+            .unwrap()
+    }
+    pub async fn get_fit(self) -> ImageFit {
+        send_request(self.0, ImageOp::GetFit).await.unwrap()
+    }
+    pub async fn set_fit(self, fit: ImageFit) {
+        send_request(self.0, ImageOp::SetFit(fit)).await.unwrap()
+    }
+}
+
+pub struct Image<S: ImageSkin> {
+    state: ImageState,
+    skin: S,
+    reg: ServiceRegistration,
+}
+
+impl<S: ImageSkin> Image<S> {
+    pub fn new() -> Self {
+        Self {
+            state: ImageState::default(),
+            skin: S::default(),
+            reg: register_service(),
+        }
+    }
+    pub fn id(&self) -> ImageId {
+        ImageId(self.reg.id())
+    }
+    pub fn set_image(&mut self, image: Option<graphics::Image>) {
+        self.state.image = image
+    }
+    pub fn get_image(&self) -> Option<&graphics::Image> {
+        self.state.image.as_ref()
+    }
+    pub fn set_fit(&mut self, fit: ImageFit) {
+        self.state.fit = fit
+    }
+    pub fn get_fit(&self) -> ImageFit {
+        self.state.fit
+    }
+}
+
+impl<S: ImageSkin> Default for Image<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ImageSkin + 'static> Widget for Image<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        let size = match &self.state.image {
+            Some(image) => format!("{}x{}", image.width(), image.height()),
+            None => "none".to_string(),
+        };
+        vec![("image", size), ("fit", format!("{:?}", self.state.fit))]
+    }
+}
+
+impl<S: ImageSkin + 'static> EventHandlerProxy for Image<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            ImageOp::SetImage(image) => {
+                self.set_image(image);
+                Some(Box::new(()))
+            }
+            ImageOp::GetFit => Some(Box::new(self.get_fit())),
+            ImageOp::SetFit(fit) => {
+                self.set_fit(fit);
+                Some(Box::new(()))
+            }
+        });
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+}
+
+pub struct ImageBuilder<S: ImageSkin> {
+    image: Image<S>,
+}
+
+impl<S: ImageSkin> Default for ImageBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ImageSkin> ImageBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            image: Image::new(),
+        }
+    }
+    pub fn set_image(mut self, image: graphics::Image) -> Self {
+        self.image.set_image(Some(image));
+        self
+    }
+    pub fn set_fit(mut self, fit: ImageFit) -> Self {
+        self.image.set_fit(fit);
+        self
+    }
+    pub fn build(self) -> Image<S> {
+        self.image
+    }
+}
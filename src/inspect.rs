@@ -0,0 +1,369 @@
+use crate::Widget;
+use async_call::SrvId;
+use ggez::graphics::{
+    self, Color, DrawMode, DrawParam, MeshBuilder, Rect, Scale, Text, TextFragment,
+};
+use ggez::input::mouse;
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+use std::fmt::Write;
+
+#[derive(Clone, Debug)]
+pub struct WidgetInfo {
+    pub type_name: String,
+    pub srv_id: SrvId,
+    pub rect: Option<Rect>,
+    pub properties: Vec<(&'static str, String)>,
+    pub children: Vec<WidgetInfo>,
+}
+
+pub fn short_type_name(name: &str) -> String {
+    let mut result = String::new();
+    let mut segment = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else if c == ':' {
+            segment.clear();
+        } else {
+            result.push_str(&segment);
+            segment.clear();
+            result.push(c);
+        }
+    }
+    result.push_str(&segment);
+    result
+}
+
+fn json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn json_number(out: &mut String, v: f32) {
+    if v.is_finite() {
+        write!(out, "{}", v).unwrap()
+    } else {
+        out.push_str("null")
+    }
+}
+
+impl WidgetInfo {
+    pub fn new(widget: &dyn Widget) -> Self {
+        Self {
+            type_name: short_type_name(widget.type_name()),
+            srv_id: widget.srv_id(),
+            rect: widget.get_rect(),
+            properties: widget.properties(),
+            children: widget.children().into_iter().map(WidgetInfo::new).collect(),
+        }
+    }
+
+    /// One line per widget, children indented by two spaces
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        self.write_text(&mut out, 0);
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    fn write_text(&self, out: &mut String, depth: usize) {
+        write!(
+            out,
+            "{:indent$}{} {:?}",
+            "",
+            self.type_name,
+            self.srv_id,
+            indent = depth * 2
+        )
+        .unwrap();
+        if let Some(rect) = self.rect {
+            write!(out, " [{}, {}, {}x{}]", rect.x, rect.y, rect.w, rect.h).unwrap();
+        }
+        for (name, value) in &self.properties {
+            write!(out, " {}={:?}", name, value).unwrap();
+        }
+        out.push('\n');
+        for child in &self.children {
+            child.write_text(out, depth + 1);
+        }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        out.push_str("{\"type\":");
+        json_string(out, &self.type_name);
+        out.push_str(",\"srv_id\":");
+        json_string(out, &format!("{:?}", self.srv_id));
+        out.push_str(",\"rect\":");
+        if let Some(rect) = self.rect {
+            out.push_str("{\"x\":");
+            json_number(out, rect.x);
+            out.push_str(",\"y\":");
+            json_number(out, rect.y);
+            out.push_str(",\"w\":");
+            json_number(out, rect.w);
+            out.push_str(",\"h\":");
+            json_number(out, rect.h);
+            out.push('}');
+        } else {
+            out.push_str("null");
+        }
+        out.push_str(",\"properties\":{");
+        for (n, (name, value)) in self.properties.iter().enumerate() {
+            if n > 0 {
+                out.push(',');
+            }
+            json_string(out, name);
+            out.push(':');
+            json_string(out, value);
+        }
+        out.push_str("},\"children\":[");
+        for (n, child) in self.children.iter().enumerate() {
+            if n > 0 {
+                out.push(',');
+            }
+            child.write_json(out);
+        }
+        out.push_str("]}");
+    }
+}
+
+const OUTLINE_COLORS: [Color; 4] = [
+    Color::new(1., 0.3, 0.3, 0.8),
+    Color::new(0.3, 1., 0.3, 0.8),
+    Color::new(0.3, 0.6, 1., 0.8),
+    Color::new(1., 0.3, 1., 0.8),
+];
+const HIGHLIGHT_COLOR: Color = Color::new(1., 1., 0., 1.);
+const HIGHLIGHT_FILL_COLOR: Color = Color::new(1., 1., 0., 0.2);
+const PANEL_COLOR: Color = Color::new(0., 0., 0., 0.8);
+const TEXT_SIZE: f32 = 12.;
+const PANEL_OFFSET: f32 = 16.;
+
+fn small_text(s: String, color: Color) -> Text {
+    Text::new(
+        TextFragment::new(s)
+            .color(color)
+            .scale(Scale::uniform(TEXT_SIZE)),
+    )
+}
+
+fn collect_outlines(widget: &dyn Widget, depth: usize, out: &mut Vec<(usize, Rect, String)>) {
+    if let Some(rect) = widget.get_rect() {
+        let label = format!(
+            "{} {:?}",
+            short_type_name(widget.type_name()),
+            widget.srv_id()
+        );
+        out.push((depth, rect, label));
+    }
+    for child in widget.children() {
+        collect_outlines(child, depth + 1, out);
+    }
+}
+
+fn describe(widget: &dyn Widget) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{} {:?}",
+        short_type_name(widget.type_name()),
+        widget.srv_id()
+    )];
+    if let Some(rect) = widget.get_rect() {
+        lines.push(format!(
+            "rect: [{}, {}, {}x{}]",
+            rect.x, rect.y, rect.w, rect.h
+        ));
+    }
+    for (name, value) in widget.properties() {
+        lines.push(format!("{}: {:?}", name, value));
+    }
+    lines
+}
+
+/// Debug overlay which outlines every widget and describes the one under the cursor.
+/// Draw it after the root widget.
+#[derive(Default)]
+pub struct Inspector {
+    enabled: bool,
+}
+
+impl Inspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled
+    }
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled
+    }
+
+    pub fn draw(&self, ctx: &mut Context, root: &dyn Widget) -> GameResult {
+        if !self.enabled {
+            return Ok(());
+        }
+        let mut outlines = Vec::new();
+        collect_outlines(root, 0, &mut outlines);
+        let mouse = mouse::position(ctx);
+        let hovered = root.widget_at(mouse.x, mouse.y);
+        let hovered_rect = hovered.and_then(|w| w.get_rect());
+
+        let mut mb = MeshBuilder::new();
+        for (depth, rect, _) in &outlines {
+            mb.rectangle(
+                DrawMode::stroke(1.),
+                *rect,
+                OUTLINE_COLORS[depth % OUTLINE_COLORS.len()],
+            );
+        }
+        if let Some(rect) = hovered_rect {
+            mb.rectangle(DrawMode::fill(), rect, HIGHLIGHT_FILL_COLOR);
+            mb.rectangle(DrawMode::stroke(2.), rect, HIGHLIGHT_COLOR);
+        }
+        if !outlines.is_empty() || hovered_rect.is_some() {
+            let mesh = mb.build(ctx)?;
+            graphics::draw(ctx, &mesh, DrawParam::default())?;
+        }
+        for (depth, rect, label) in outlines {
+            let text = small_text(label, OUTLINE_COLORS[depth % OUTLINE_COLORS.len()]);
+            graphics::draw(
+                ctx,
+                &text,
+                DrawParam::default().dest(Point2::new(rect.x + 2., rect.y + 2.)),
+            )?;
+        }
+
+        if let Some(widget) = hovered {
+            let mut lines = describe(widget);
+            if let Some(parent) = root.find_parent(widget.srv_id()) {
+                lines.push(String::new());
+                lines.push("parent:".to_string());
+                lines.extend(describe(parent));
+            }
+            let text = small_text(lines.join("\n"), graphics::WHITE);
+            let (w, h) = text.dimensions(ctx);
+            let (w, h) = (w as f32 + 8., h as f32 + 8.);
+            let screen = graphics::screen_coordinates(ctx);
+            let x = (mouse.x + PANEL_OFFSET)
+                .min(screen.right() - w)
+                .max(screen.x);
+            let y = (mouse.y + PANEL_OFFSET)
+                .min(screen.bottom() - h)
+                .max(screen.y);
+            let panel = MeshBuilder::new()
+                .rectangle(DrawMode::fill(), Rect::new(x, y, w, h), PANEL_COLOR)
+                .build(ctx)?;
+            graphics::draw(ctx, &panel, DrawParam::default())?;
+            graphics::draw(
+                ctx,
+                &text,
+                DrawParam::default().dest(Point2::new(x + 4., y + 4.)),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_call::register_service;
+
+    fn info(type_name: &str, srv_id: SrvId, children: Vec<WidgetInfo>) -> WidgetInfo {
+        WidgetInfo {
+            type_name: type_name.to_string(),
+            srv_id,
+            rect: None,
+            properties: Vec::new(),
+            children,
+        }
+    }
+
+    #[test]
+    fn short_type_name_strips_module_paths() {
+        assert_eq!(short_type_name("yorool_gui2::ribbon::Ribbon"), "Ribbon");
+        assert_eq!(
+            short_type_name(
+                "yorool_gui2::button::Button<yorool_gui2::default_skin::button::DefaultButtonSkin>"
+            ),
+            "Button<DefaultButtonSkin>"
+        );
+        assert_eq!(
+            short_type_name("a::Pair<b::X, (c::Y, &d::Z)>"),
+            "Pair<X, (Y, &Z)>"
+        );
+        assert_eq!(short_type_name("u32"), "u32");
+    }
+
+    #[test]
+    fn to_text_indents_children() {
+        let (a, b, c, d) = (
+            register_service(),
+            register_service(),
+            register_service(),
+            register_service(),
+        );
+        let mut leaf = info("Leaf", c.id(), Vec::new());
+        leaf.rect = Some(Rect::new(1., 2., 30., 40.));
+        leaf.properties = vec![("label", "a \"b\"".to_string())];
+        let tree = info(
+            "Root",
+            a.id(),
+            vec![
+                info("Group", b.id(), vec![leaf]),
+                info("Last", d.id(), Vec::new()),
+            ],
+        );
+        let expected = format!(
+            "Root {:?}\n  Group {:?}\n    Leaf {:?} [1, 2, 30x40] label=\"a \\\"b\\\"\"\n  Last {:?}\n",
+            a.id(),
+            b.id(),
+            c.id(),
+            d.id()
+        );
+        assert_eq!(tree.to_text(), expected);
+    }
+
+    #[test]
+    fn to_json_nests_children_and_escapes_strings() {
+        let (a, b) = (register_service(), register_service());
+        let mut child = info("Child", b.id(), Vec::new());
+        child.rect = Some(Rect::new(0., 0.5, 10., f32::INFINITY));
+        child.properties = vec![
+            ("label", "say \"hi\"\\\n\t\u{1}".to_string()),
+            ("checked", "true".to_string()),
+        ];
+        let tree = info("Root", a.id(), vec![child]);
+        let expected = format!(
+            concat!(
+                "{{\"type\":\"Root\",\"srv_id\":\"{:?}\",\"rect\":null,\"properties\":{{}},",
+                "\"children\":[{{\"type\":\"Child\",\"srv_id\":\"{:?}\",",
+                "\"rect\":{{\"x\":0,\"y\":0.5,\"w\":10,\"h\":null}},",
+                "\"properties\":{{\"label\":\"say \\\"hi\\\"\\\\\\n\\t\\u0001\",\"checked\":\"true\"}},",
+                "\"children\":[]}}]}}"
+            ),
+            a.id(),
+            b.id()
+        );
+        assert_eq!(tree.to_json(), expected);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::graphics::Rect;
+use ggez::{Context, GameResult};
+use std::fmt::Debug;
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum HorizontalAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum VerticalAlign {
+    Top,
+    #[default]
+    Center,
+    Bottom,
+}
+
+/// What to do with the text which doesn't fit into label's width
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum TextOverflow {
+    #[default]
+    Wrap,
+    Ellipsis,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct LabelState {
+    pub text: String,
+    pub horizontal_align: HorizontalAlign,
+    pub vertical_align: VerticalAlign,
+    pub overflow: TextOverflow,
+    pub rect: Rect,
+}
+
+pub trait LabelSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &LabelState);
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LabelId(SrvId);
+
+impl From<LabelId> for SrvId {
+    fn from(v: LabelId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum LabelOp {
+    GetText,
+    SetText(String),
+    SetHorizontalAlign(HorizontalAlign),
+    SetVerticalAlign(VerticalAlign),
+    SetOverflow(TextOverflow),
+}
+
+impl LabelId {
+    pub async fn get_text(self) -> String {
+        send_request(self.0, LabelOp::GetText).await.unwrap()
+    }
+    pub async fn set_text(self, text: String) {
+        send_request(self.0, LabelOp::SetText(text)).await.unwrap()
+    }
+    pub async fn set_horizontal_align(self, align: HorizontalAlign) {
+        send_request(self.0, LabelOp::SetHorizontalAlign(align))
+            .await
+            .unwrap()
+    }
+    pub async fn set_vertical_align(self, align: VerticalAlign) {
+        send_request(self.0, LabelOp::SetVerticalAlign(align))
+            .await
+            .unwrap()
+    }
+    pub async fn set_overflow(self, overflow: TextOverflow) {
+        send_request(self.0, LabelOp::SetOverflow(overflow))
+            .await
+            .unwrap()
+    }
+}
+
+pub struct Label<S: LabelSkin> {
+    state: LabelState,
+    skin: S,
+    reg: ServiceRegistration,
+}
+
+impl<S: LabelSkin> Label<S> {
+    pub fn new() -> Self {
+        Self {
+            state: LabelState::default(),
+            skin: S::default(),
+            reg: register_service(),
+        }
+    }
+    pub fn id(&self) -> LabelId {
+        LabelId(self.reg.id())
+    }
+    pub fn set_text(&mut self, text: String) {
+        self.state.text = text
+    }
+    pub fn get_text(&self) -> &str {
+        self.state.text.as_str()
+    }
+    pub fn set_horizontal_align(&mut self, align: HorizontalAlign) {
+        self.state.horizontal_align = align
+    }
+    pub fn get_horizontal_align(&self) -> HorizontalAlign {
+        self.state.horizontal_align
+    }
+    pub fn set_vertical_align(&mut self, align: VerticalAlign) {
+        self.state.vertical_align = align
+    }
+    pub fn get_vertical_align(&self) -> VerticalAlign {
+        self.state.vertical_align
+    }
+    pub fn set_overflow(&mut self, overflow: TextOverflow) {
+        self.state.overflow = overflow
+    }
+    pub fn get_overflow(&self) -> TextOverflow {
+        self.state.overflow
+    }
+}
+
+impl<S: LabelSkin> Default for Label<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: LabelSkin + 'static> Widget for Label<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![("text", self.state.text.clone())]
+    }
+}
+
+impl<S: LabelSkin + 'static> EventHandlerProxy for Label<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            LabelOp::GetText => Some(Box::new(self.get_text().to_string())),
+            LabelOp::SetText(text) => {
+                self.set_text(text);
+                Some(Box::new(()))
+            }
+            LabelOp::SetHorizontalAlign(align) => {
+                self.set_horizontal_align(align);
+                Some(Box::new(()))
+            }
+            LabelOp::SetVerticalAlign(align) => {
+                self.set_vertical_align(align);
+                Some(Box::new(()))
+            }
+            LabelOp::SetOverflow(overflow) => {
+                self.set_overflow(overflow);
+                Some(Box::new(()))
+            }
+        });
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+}
+
+pub struct LabelBuilder<S: LabelSkin> {
+    label: Label<S>,
+}
+
+impl<S: LabelSkin> Default for LabelBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: LabelSkin> LabelBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            label: Label::new(),
+        }
+    }
+    pub fn set_text<T: Into<String>>(mut self, text: T) -> Self {
+        self.label.set_text(text.into());
+        self
+    }
+    pub fn set_horizontal_align(mut self, align: HorizontalAlign) -> Self {
+        self.label.set_horizontal_align(align);
+        self
+    }
+    pub fn set_vertical_align(mut self, align: VerticalAlign) -> Self {
+        self.label.set_vertical_align(align);
+        self
+    }
+    pub fn set_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.label.set_overflow(overflow);
+        self
+    }
+    pub fn build(self) -> Label<S> {
+        self.label
+    }
+}
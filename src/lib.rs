@@ -1,15 +1,46 @@
+use crate::inspect::short_type_name;
+use crate::overlay::OverlayId;
 use async_call::SrvId;
 use ggez::event::{EventHandler, MouseButton};
 use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::nalgebra::Point2;
 use ggez::{Context, GameResult};
 use indexmap::map::IndexMap;
 use std::fmt::{Debug, Formatter};
 
 pub mod button;
+pub mod canvas;
+pub mod combobox;
+pub mod contextmenu;
 pub mod default_skin;
+pub mod dialog;
+pub mod guiroot;
+pub mod image;
+pub mod inspect;
+pub mod label;
+pub mod listview;
+pub mod menu;
+pub mod menubar;
+pub mod overlay;
+pub mod progressbar;
 pub mod radiogroup;
 pub mod ribbon;
+pub mod slider;
+pub mod spinner;
+pub mod splitter;
+pub mod stack;
+pub mod table;
+pub mod tabs;
+pub mod textarea;
+pub mod textedit;
+pub mod textinput;
+pub mod tooltip;
+pub mod treeview;
+pub mod typeahead;
+pub mod virtuallist;
 
+/// Input event handlers return `true` when the event was consumed by the widget
 pub trait EventHandlerProxy {
     fn update(&mut self, _ctx: &mut Context) -> GameResult {
         Ok(())
@@ -23,7 +54,8 @@ pub trait EventHandlerProxy {
         _button: MouseButton,
         _x: f32,
         _y: f32,
-    ) {
+    ) -> bool {
+        false
     }
     fn mouse_button_up_event(
         &mut self,
@@ -31,7 +63,41 @@ pub trait EventHandlerProxy {
         _button: MouseButton,
         _x: f32,
         _y: f32,
-    ) {
+    ) -> bool {
+        false
+    }
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        _x: f32,
+        _y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> bool {
+        false
+    }
+    fn mouse_wheel_event(&mut self, _ctx: &mut Context, _x: f32, _y: f32) -> bool {
+        false
+    }
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        _keycode: KeyCode,
+        _keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        false
+    }
+    /// Key press offered to every widget before the regular `key_down_event`
+    /// dispatch, used for accelerators working regardless of focus
+    fn shortcut_event(&mut self, _ctx: &mut Context, _keycode: KeyCode, _keymods: KeyMods) -> bool {
+        false
+    }
+    fn key_up_event(&mut self, _ctx: &mut Context, _keycode: KeyCode, _keymods: KeyMods) -> bool {
+        false
+    }
+    fn text_input_event(&mut self, _ctx: &mut Context, _character: char) -> bool {
+        false
     }
 }
 
@@ -41,10 +107,132 @@ pub trait Widget: EventHandlerProxy + Send {
     fn get_rect(&self) -> Option<Rect> {
         None
     }
+    fn children(&self) -> Vec<&dyn Widget> {
+        Vec::new()
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        Vec::new()
+    }
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+    /// Text shown near the mouse when it stays over the widget. This is the
+    /// extension point for tooltips: widgets keep the text themselves, like
+    /// `Button` with its `set_tooltip`, and return it here.
+    fn tooltip(&self) -> Option<&str> {
+        None
+    }
+    /// Widget specific state shown in tree dumps and in the inspector
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+    /// Called once when the widget gets into the tree of a `GuiRoot` or into
+    /// a popup. Widgets opening popups keep the overlay, containers pass it
+    /// on to all their children, including ones added later.
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        for w in self.children_mut() {
+            w.attach_overlay(overlay)
+        }
+    }
+}
+
+impl<'a> dyn Widget + 'a {
+    /// Searches the subtree rooted at this widget (including the widget itself)
+    pub fn find_widget(&self, id: SrvId) -> Option<&dyn Widget> {
+        if self.srv_id() == id {
+            return Some(self);
+        }
+        self.children().into_iter().find_map(|w| w.find_widget(id))
+    }
+    pub fn find_widget_mut(&mut self, id: SrvId) -> Option<&mut dyn Widget> {
+        if self.srv_id() == id {
+            return Some(self);
+        }
+        self.children_mut()
+            .into_iter()
+            .find_map(|w| w.find_widget_mut(id))
+    }
+    /// Returns the widget which has the widget `id` as direct child
+    pub fn find_parent(&self, id: SrvId) -> Option<&dyn Widget> {
+        let children = self.children();
+        if children.iter().any(|w| w.srv_id() == id) {
+            return Some(self);
+        }
+        children.into_iter().find_map(|w| w.find_parent(id))
+    }
+    /// Returns the innermost widget which rect contains the point
+    pub fn widget_at(&self, x: f32, y: f32) -> Option<&dyn Widget> {
+        let inside = self.get_rect().map(|r| r.contains(Point2::new(x, y)));
+        if inside == Some(false) {
+            return None;
+        }
+        let child = self
+            .children()
+            .into_iter()
+            .rev()
+            .find_map(|w| w.widget_at(x, y));
+        if child.is_some() {
+            child
+        } else if inside == Some(true) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+    /// Offers the shortcut to the widget and its subtree until someone takes it
+    pub fn dispatch_shortcut(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+    ) -> bool {
+        self.shortcut_event(ctx, keycode, keymods)
+            || self
+                .children_mut()
+                .into_iter()
+                .any(|w| w.dispatch_shortcut(ctx, keycode, keymods))
+    }
+    /// Returns the innermost widget with tooltip which rect contains the point
+    pub fn tooltip_owner_at(&self, x: f32, y: f32) -> Option<&dyn Widget> {
+        let inside = self.get_rect().map(|r| r.contains(Point2::new(x, y)));
+        if inside == Some(false) {
+            return None;
+        }
+        let child = self
+            .children()
+            .into_iter()
+            .rev()
+            .find_map(|w| w.tooltip_owner_at(x, y));
+        if child.is_some() {
+            child
+        } else if inside == Some(true) && self.tooltip().is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+    /// Returns ids of all widgets from this one down to the widget `id` inclusive
+    pub fn path_to(&self, id: SrvId) -> Option<Vec<SrvId>> {
+        if self.srv_id() == id {
+            return Some(vec![id]);
+        }
+        self.children().into_iter().find_map(|w| {
+            w.path_to(id).map(|mut path| {
+                path.insert(0, self.srv_id());
+                path
+            })
+        })
+    }
 }
+
 impl Debug for dyn Widget {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "WIDGET {:?}", self.srv_id())
+        write!(
+            f,
+            "{} {:?}",
+            short_type_name(self.type_name()),
+            self.srv_id()
+        )
     }
 }
 
@@ -58,14 +246,41 @@ impl EventHandler for dyn Widget {
     }
 
     fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
-        self.mouse_button_down_event(ctx, button, x, y)
+        self.mouse_button_down_event(ctx, button, x, y);
     }
 
     fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
-        self.mouse_button_up_event(ctx, button, x, y)
+        self.mouse_button_up_event(ctx, button, x, y);
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) {
+        self.mouse_motion_event(ctx, x, y, dx, dy);
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) {
+        self.mouse_wheel_event(ctx, x, y);
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) {
+        self.key_down_event(ctx, keycode, keymods, repeat);
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) {
+        self.key_up_event(ctx, keycode, keymods);
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) {
+        self.text_input_event(ctx, character);
     }
 }
 
+#[allow(clippy::borrowed_box)]
 pub trait WidgetGroup: Send {
     fn mut_root(&mut self) -> &mut Box<dyn Widget>;
     fn root(&self) -> &Box<dyn Widget>;
@@ -83,13 +298,56 @@ where
         self.mut_root().draw(ctx)
     }
 
-    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
         self.mut_root().mouse_button_down_event(ctx, button, x, y)
     }
 
-    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
         self.mut_root().mouse_button_up_event(ctx, button, x, y)
     }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        self.mut_root().mouse_motion_event(ctx, x, y, dx, dy)
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        self.mut_root().mouse_wheel_event(ctx, x, y)
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        self.mut_root()
+            .key_down_event(ctx, keycode, keymods, repeat)
+    }
+
+    fn shortcut_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        self.mut_root().shortcut_event(ctx, keycode, keymods)
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        self.mut_root().key_up_event(ctx, keycode, keymods)
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        self.mut_root().text_input_event(ctx, character)
+    }
 }
 
 impl<T> Widget for T
@@ -105,14 +363,191 @@ where
     fn get_rect(&self) -> Option<Rect> {
         self.root().get_rect()
     }
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.root().children()
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.mut_root().children_mut()
+    }
+    fn tooltip(&self) -> Option<&str> {
+        self.root().tooltip()
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        self.root().properties()
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.mut_root().attach_overlay(overlay)
+    }
 }
 
 pub(crate) fn add_to_indexmap<T>(indexmap: &mut IndexMap<usize, T>, value: T) -> usize {
     let key = if indexmap.is_empty() {
-        0 as usize
+        0
     } else {
         indexmap.get_index(indexmap.len() - 1).unwrap().0 + 1
     };
     indexmap.insert(key, value);
     key
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::overlay::Overlay;
+    use crate::ribbon::{Ribbon, RibbonOrientation};
+    use async_call::{register_service, ServiceRegistration};
+    use std::sync::{Arc, Mutex};
+
+    struct Group {
+        root: Box<dyn Widget>,
+    }
+
+    impl WidgetGroup for Group {
+        fn mut_root(&mut self) -> &mut Box<dyn Widget> {
+            &mut self.root
+        }
+        fn root(&self) -> &Box<dyn Widget> {
+            &self.root
+        }
+    }
+
+    /// Leaf which reports the overlay it got
+    struct OverlayUser {
+        reg: ServiceRegistration,
+        overlay: Arc<Mutex<Option<OverlayId>>>,
+    }
+
+    impl OverlayUser {
+        fn new() -> (Self, Arc<Mutex<Option<OverlayId>>>) {
+            let overlay = Arc::new(Mutex::new(None));
+            let user = Self {
+                reg: register_service(),
+                overlay: overlay.clone(),
+            };
+            (user, overlay)
+        }
+    }
+
+    impl EventHandlerProxy for OverlayUser {}
+
+    impl Widget for OverlayUser {
+        fn srv_id(&self) -> SrvId {
+            self.reg.id()
+        }
+        fn attach_overlay(&mut self, overlay: OverlayId) {
+            *self.overlay.lock().unwrap() = Some(overlay)
+        }
+    }
+
+    /// Horizontal ribbon with a vertical ribbon of two leaves and a group
+    /// wrapping a ribbon with one leaf
+    struct Tree {
+        root: Ribbon,
+        column: SrvId,
+        leaves: [SrvId; 2],
+        group: SrvId,
+        group_leaf: SrvId,
+    }
+
+    fn tree() -> Tree {
+        let mut column = Ribbon::new();
+        column.set_orientation(RibbonOrientation::Vertical);
+        let leaves = [Ribbon::new(), Ribbon::new()];
+        let leaf_ids = [leaves[0].srv_id(), leaves[1].srv_id()];
+        for leaf in leaves {
+            column.add_widget(leaf);
+        }
+        let mut group_root = Ribbon::new();
+        let group_leaf = Ribbon::new();
+        let group_leaf_id = group_leaf.srv_id();
+        group_root.add_widget(group_leaf);
+        let group = Group {
+            root: Box::new(group_root),
+        };
+        let column_id = column.srv_id();
+        let group_id = group.srv_id();
+        let mut root = Ribbon::new();
+        root.add_widget(column);
+        root.add_widget(group);
+        root.set_rect(Rect::new(0., 0., 100., 50.));
+        Tree {
+            root,
+            column: column_id,
+            leaves: leaf_ids,
+            group: group_id,
+            group_leaf: group_leaf_id,
+        }
+    }
+
+    #[test]
+    fn find_widget_searches_whole_subtree() {
+        let mut tree = tree();
+        let root: &dyn Widget = &tree.root;
+        for id in [tree.column, tree.leaves[1], tree.group, tree.group_leaf] {
+            assert_eq!(root.find_widget(id).map(|w| w.srv_id()), Some(id));
+        }
+        assert!(root.find_widget(Ribbon::new().srv_id()).is_none());
+        let root: &mut dyn Widget = &mut tree.root;
+        let leaf = root.find_widget_mut(tree.group_leaf).unwrap();
+        leaf.set_rect(Rect::new(1., 2., 3., 4.));
+        assert_eq!(leaf.get_rect(), Some(Rect::new(1., 2., 3., 4.)));
+    }
+
+    #[test]
+    fn find_parent_returns_direct_parent() {
+        let tree = tree();
+        let root: &dyn Widget = &tree.root;
+        let parent = |id| root.find_parent(id).map(|w| w.srv_id());
+        assert_eq!(parent(tree.leaves[0]), Some(tree.column));
+        assert_eq!(parent(tree.column), Some(root.srv_id()));
+        // group forwards to its root, so the group stands for it in the tree
+        assert_eq!(parent(tree.group_leaf), Some(tree.group));
+        assert_eq!(parent(root.srv_id()), None);
+        assert_eq!(parent(Ribbon::new().srv_id()), None);
+    }
+
+    #[test]
+    fn widget_at_returns_innermost_widget() {
+        let tree = tree();
+        let root: &dyn Widget = &tree.root;
+        let at = |x, y| root.widget_at(x, y).map(|w| w.srv_id());
+        assert_eq!(at(10., 10.), Some(tree.leaves[0]));
+        assert_eq!(at(10., 30.), Some(tree.leaves[1]));
+        assert_eq!(at(75., 10.), Some(tree.group_leaf));
+        assert_eq!(at(200., 10.), None);
+    }
+
+    #[test]
+    fn path_to_lists_ids_from_root() {
+        let tree = tree();
+        let root: &dyn Widget = &tree.root;
+        assert_eq!(
+            root.path_to(tree.leaves[1]),
+            Some(vec![root.srv_id(), tree.column, tree.leaves[1]])
+        );
+        assert_eq!(
+            root.path_to(tree.group_leaf),
+            Some(vec![root.srv_id(), tree.group, tree.group_leaf])
+        );
+        assert_eq!(root.path_to(root.srv_id()), Some(vec![root.srv_id()]));
+        assert_eq!(root.path_to(Ribbon::new().srv_id()), None);
+    }
+
+    #[test]
+    fn attach_overlay_reaches_nested_and_later_added_widgets() {
+        let overlay = Overlay::new().id();
+        let (nested, nested_overlay) = OverlayUser::new();
+        let mut group_root = Ribbon::new();
+        group_root.add_widget(nested);
+        let mut root = Ribbon::new();
+        root.add_widget(Group {
+            root: Box::new(group_root),
+        });
+        let root_widget: &mut dyn Widget = &mut root;
+        root_widget.attach_overlay(overlay);
+        assert_eq!(*nested_overlay.lock().unwrap(), Some(overlay));
+        let (added, added_overlay) = OverlayUser::new();
+        root.add_widget(added);
+        assert_eq!(*added_overlay.lock().unwrap(), Some(overlay));
+    }
+}
@@ -0,0 +1,567 @@
+use crate::typeahead::TypeAhead;
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{self, KeyCode, KeyMods};
+use ggez::input::mouse;
+use ggez::{timer, Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+
+const WHEEL_STEP: usize = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum SelectionMode {
+    #[default]
+    Single,
+    /// Ctrl-click toggles items, Shift-click selects range
+    Multi,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ListViewState {
+    pub items: Vec<String>,
+    /// Selected indices in ascending order
+    pub selection: Vec<usize>,
+    /// Item with keyboard cursor
+    pub current: Option<usize>,
+    /// Index of the first visible item
+    pub scroll: usize,
+    pub focused: bool,
+    pub rect: Rect,
+}
+
+impl ListViewState {
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selection.binary_search(&index).is_ok()
+    }
+}
+
+pub trait ListViewSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &ListViewState);
+    fn is_hot_area(&self, x: f32, y: f32) -> bool;
+    fn item_at(&self, x: f32, y: f32) -> Option<usize>;
+    /// Number of fully visible rows
+    fn visible_count(&self) -> usize;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ListViewId(SrvId);
+
+impl From<ListViewId> for SrvId {
+    fn from(v: ListViewId) -> SrvId {
+        v.0
+    }
+}
+
+type SelectionChangedHandler = Box<dyn Fn(&mut dyn Widget, &[usize]) + Send + Sync>;
+
+enum ListViewOp {
+    GetItems,
+    SetItems(Vec<String>),
+    Insert(usize, String),
+    Remove(usize),
+    GetSelection,
+    SetSelection(Vec<usize>),
+    OnSelectionChanged(SelectionChangedHandler),
+    RemoveOnSelectionChanged(usize),
+}
+
+impl Debug for ListViewOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListViewOp::GetItems => write!(f, "GetItems"),
+            ListViewOp::SetItems(items) => write!(f, "SetItems({:?})", items),
+            ListViewOp::Insert(index, item) => write!(f, "Insert({:?}, {:?})", index, item),
+            ListViewOp::Remove(index) => write!(f, "Remove({:?})", index),
+            ListViewOp::GetSelection => write!(f, "GetSelection"),
+            ListViewOp::SetSelection(selection) => write!(f, "SetSelection({:?})", selection),
+            ListViewOp::OnSelectionChanged(_) => write!(f, "OnSelectionChanged"),
+            ListViewOp::RemoveOnSelectionChanged(handler_id) => {
+                write!(f, "RemoveOnSelectionChanged({:?})", handler_id)
+            }
+        }
+    }
+}
+
+impl ListViewId {
+    pub async fn get_items(self) -> Vec<String> {
+        send_request(self.0, ListViewOp::GetItems).await.unwrap()
+    }
+    pub async fn set_items(self, items: Vec<String>) {
+        send_request(self.0, ListViewOp::SetItems(items))
+            .await
+            .unwrap()
+    }
+    pub async fn insert(self, index: usize, item: String) {
+        send_request(self.0, ListViewOp::Insert(index, item))
+            .await
+            .unwrap()
+    }
+    pub async fn remove(self, index: usize) -> Option<String> {
+        send_request(self.0, ListViewOp::Remove(index))
+            .await
+            .unwrap()
+    }
+    pub async fn get_selection(self) -> Vec<usize> {
+        send_request(self.0, ListViewOp::GetSelection)
+            .await
+            .unwrap()
+    }
+    pub async fn set_selection(self, selection: Vec<usize>) {
+        send_request(self.0, ListViewOp::SetSelection(selection))
+            .await
+            .unwrap()
+    }
+    pub async fn on_selection_changed<F: Fn(&mut dyn Widget, &[usize]) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, ListViewOp::OnSelectionChanged(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_selection_changed(self, handler_id: usize) {
+        send_request(self.0, ListViewOp::RemoveOnSelectionChanged(handler_id))
+            .await
+            .unwrap()
+    }
+}
+
+/// Scrollable list of text items with single or multiple selection
+pub struct ListView<S: ListViewSkin> {
+    state: ListViewState,
+    skin: S,
+    reg: ServiceRegistration,
+    mode: SelectionMode,
+    /// Start of the range selected with Shift
+    anchor: Option<usize>,
+    type_ahead: TypeAhead,
+    on_selection_changed_handlers: IndexMap<usize, SelectionChangedHandler>,
+}
+
+impl<S: ListViewSkin> ListView<S> {
+    pub fn new() -> Self {
+        Self {
+            state: ListViewState::default(),
+            skin: S::default(),
+            reg: register_service(),
+            mode: SelectionMode::default(),
+            anchor: None,
+            type_ahead: TypeAhead::new(),
+            on_selection_changed_handlers: IndexMap::new(),
+        }
+    }
+    pub fn id(&self) -> ListViewId {
+        ListViewId(self.reg.id())
+    }
+    /// Drops selection out of new items range
+    pub fn set_items(&mut self, items: Vec<String>) {
+        let len = items.len();
+        self.state.items = items;
+        self.state.selection.retain(|&n| n < len);
+        self.state.current = self.state.current.filter(|&n| n < len);
+        self.anchor = self.anchor.filter(|&n| n < len);
+        self.state.scroll = self.state.scroll.min(len.saturating_sub(1));
+    }
+    pub fn get_items(&self) -> &[String] {
+        &self.state.items
+    }
+    /// Inserts item before `index` or at the end, selection moves with items
+    pub fn insert(&mut self, index: usize, item: String) {
+        let index = index.min(self.state.items.len());
+        self.state.items.insert(index, item);
+        let shift = |n: usize| if n >= index { n + 1 } else { n };
+        for n in &mut self.state.selection {
+            *n = shift(*n)
+        }
+        self.state.current = self.state.current.map(shift);
+        self.anchor = self.anchor.map(shift);
+    }
+    pub fn remove(&mut self, index: usize) -> Option<String> {
+        if index >= self.state.items.len() {
+            return None;
+        }
+        let item = self.state.items.remove(index);
+        let len = self.state.items.len();
+        let shift = |n: usize| if n > index { n - 1 } else { n };
+        self.state.selection = self
+            .state
+            .selection
+            .iter()
+            .filter(|&&n| n != index)
+            .map(|&n| shift(n))
+            .collect();
+        self.state.current = self.state.current.map(shift).filter(|&n| n < len);
+        self.anchor = self.anchor.map(shift).filter(|&n| n < len);
+        self.state.scroll = self.state.scroll.min(len.saturating_sub(1));
+        Some(item)
+    }
+    pub fn get_selection(&self) -> &[usize] {
+        &self.state.selection
+    }
+    pub fn set_selection(&mut self, selection: Vec<usize>) {
+        self.state.selection = self.normalize(selection);
+    }
+    pub fn set_selection_mode(&mut self, mode: SelectionMode) {
+        self.mode = mode;
+        let selection = self.normalize(self.state.selection.clone());
+        self.state.selection = selection;
+    }
+    pub fn get_selection_mode(&self) -> SelectionMode {
+        self.mode
+    }
+    pub fn on_selection_changed_box(&mut self, handler: SelectionChangedHandler) -> usize {
+        add_to_indexmap(&mut self.on_selection_changed_handlers, handler)
+    }
+    pub fn on_selection_changed<F: Fn(&mut dyn Widget, &[usize]) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_selection_changed_box(Box::new(f))
+    }
+    pub fn remove_on_selection_changed(&mut self, handler_id: usize) {
+        self.on_selection_changed_handlers.remove(&handler_id);
+    }
+    /// Sorted valid indices, at most one in single selection mode
+    fn normalize(&self, mut selection: Vec<usize>) -> Vec<usize> {
+        let len = self.state.items.len();
+        selection.retain(|&n| n < len);
+        selection.sort_unstable();
+        selection.dedup();
+        if self.mode == SelectionMode::Single {
+            selection.truncate(1);
+        }
+        selection
+    }
+    fn ensure_visible(&mut self, index: usize) {
+        self.skin.set_state(&self.state);
+        let visible = self.skin.visible_count().max(1);
+        if index < self.state.scroll {
+            self.state.scroll = index
+        } else if index >= self.state.scroll + visible {
+            self.state.scroll = index + 1 - visible
+        }
+    }
+    fn scroll_by(&mut self, delta: isize) {
+        self.skin.set_state(&self.state);
+        let max_scroll = self
+            .state
+            .items
+            .len()
+            .saturating_sub(self.skin.visible_count());
+        let scroll = self.state.scroll as isize + delta;
+        self.state.scroll = (scroll.max(0) as usize).min(max_scroll);
+    }
+}
+
+impl<S: ListViewSkin> Default for ListView<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ListViewSkin + 'static> ListView<S> {
+    /// Changes selection from user input and notifies handlers if it was changed
+    fn change_selection(&mut self, selection: Vec<usize>) {
+        let selection = self.normalize(selection);
+        if selection == self.state.selection {
+            return;
+        }
+        self.state.selection = selection.clone();
+        let handlers = std::mem::replace(&mut self.on_selection_changed_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, &selection);
+        }
+        self.on_selection_changed_handlers = handlers;
+    }
+    /// Moves keyboard cursor to the item, selecting it the way a click does
+    fn select_with_mods(&mut self, index: usize, keymods: KeyMods, toggle: bool) {
+        if self.state.items.is_empty() {
+            return;
+        }
+        let index = index.min(self.state.items.len() - 1);
+        self.state.current = Some(index);
+        self.ensure_visible(index);
+        if self.mode == SelectionMode::Multi {
+            if keymods.contains(KeyMods::SHIFT) {
+                let anchor = self.anchor.unwrap_or(index);
+                let range = anchor.min(index)..=anchor.max(index);
+                self.change_selection(range.collect());
+                return;
+            }
+            if keymods.contains(KeyMods::CTRL) {
+                self.anchor = Some(index);
+                if toggle {
+                    let mut selection = self.state.selection.clone();
+                    match selection.binary_search(&index) {
+                        Ok(pos) => {
+                            selection.remove(pos);
+                        }
+                        Err(pos) => selection.insert(pos, index),
+                    }
+                    self.change_selection(selection);
+                }
+                return;
+            }
+        }
+        self.anchor = Some(index);
+        self.change_selection(vec![index]);
+    }
+}
+
+impl<S: ListViewSkin + 'static> Widget for ListView<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("items", self.state.items.len().to_string()),
+            ("selection", format!("{:?}", self.state.selection)),
+        ]
+    }
+}
+
+impl<S: ListViewSkin + 'static> EventHandlerProxy for ListView<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            ListViewOp::GetItems => Some(Box::new(self.get_items().to_vec())),
+            ListViewOp::SetItems(items) => {
+                self.set_items(items);
+                Some(Box::new(()))
+            }
+            ListViewOp::Insert(index, item) => {
+                self.insert(index, item);
+                Some(Box::new(()))
+            }
+            ListViewOp::Remove(index) => Some(Box::new(self.remove(index))),
+            ListViewOp::GetSelection => Some(Box::new(self.get_selection().to_vec())),
+            ListViewOp::SetSelection(selection) => {
+                self.set_selection(selection);
+                Some(Box::new(()))
+            }
+            ListViewOp::OnSelectionChanged(handler) => {
+                Some(Box::new(self.on_selection_changed_box(handler)))
+            }
+            ListViewOp::RemoveOnSelectionChanged(handler_id) => {
+                self.remove_on_selection_changed(handler_id);
+                Some(Box::new(()))
+            }
+        });
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button != MouseButton::Left {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        if !self.skin.is_hot_area(x, y) {
+            self.state.focused = false;
+            return false;
+        }
+        self.state.focused = true;
+        if let Some(index) = self.skin.item_at(x, y) {
+            self.select_with_mods(index, keyboard::active_mods(ctx), true);
+        }
+        true
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) -> bool {
+        let pos = mouse::position(ctx);
+        self.skin.set_state(&self.state);
+        if !self.skin.is_hot_area(pos.x, pos.y) {
+            return false;
+        }
+        if y > 0. {
+            self.scroll_by(-(WHEEL_STEP as isize))
+        } else if y < 0. {
+            self.scroll_by(WHEEL_STEP as isize)
+        }
+        true
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        if !self.state.focused {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        let page = self.skin.visible_count().max(1);
+        let current = self.state.current;
+        match keycode {
+            KeyCode::Up => {
+                self.select_with_mods(current.map_or(0, |n| n.saturating_sub(1)), keymods, false)
+            }
+            KeyCode::Down => self.select_with_mods(current.map_or(0, |n| n + 1), keymods, false),
+            KeyCode::PageUp => self.select_with_mods(
+                current.map_or(0, |n| n.saturating_sub(page)),
+                keymods,
+                false,
+            ),
+            KeyCode::PageDown => {
+                self.select_with_mods(current.map_or(0, |n| n + page), keymods, false)
+            }
+            KeyCode::Home => self.select_with_mods(0, keymods, false),
+            KeyCode::End => self.select_with_mods(self.state.items.len(), keymods, false),
+            KeyCode::Space if keymods.contains(KeyMods::CTRL) => {
+                if let Some(index) = current {
+                    self.select_with_mods(index, keymods, true)
+                }
+            }
+            KeyCode::A if keymods.contains(KeyMods::CTRL) && self.mode == SelectionMode::Multi => {
+                self.change_selection((0..self.state.items.len()).collect())
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        if !self.state.focused || character.is_control() || character == ' ' {
+            return false;
+        }
+        let now = timer::time_since_start(ctx);
+        let found = self
+            .type_ahead
+            .input(now, character, &self.state.items, self.state.current);
+        if let Some(index) = found {
+            self.select_with_mods(index, KeyMods::NONE, false);
+        }
+        true
+    }
+}
+
+pub struct ListViewBuilder<S: ListViewSkin> {
+    list_view: ListView<S>,
+}
+
+impl<S: ListViewSkin + 'static> Default for ListViewBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ListViewSkin + 'static> ListViewBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            list_view: ListView::new(),
+        }
+    }
+    pub fn set_items<T: Into<String>, I: IntoIterator<Item = T>>(mut self, items: I) -> Self {
+        self.list_view
+            .set_items(items.into_iter().map(|s| s.into()).collect());
+        self
+    }
+    pub fn set_selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.list_view.set_selection_mode(mode);
+        self
+    }
+    pub fn set_selection(mut self, selection: Vec<usize>) -> Self {
+        self.list_view.set_selection(selection);
+        self
+    }
+    pub fn on_selection_changed<F: Fn(&mut dyn Widget, &[usize]) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.list_view.on_selection_changed(f);
+        self
+    }
+    pub fn build(self) -> ListView<S> {
+        self.list_view
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default, Debug)]
+    struct TestSkin;
+
+    impl EventHandlerProxy for TestSkin {}
+
+    impl ListViewSkin for TestSkin {
+        fn set_state(&mut self, _state: &ListViewState) {}
+        fn is_hot_area(&self, _x: f32, _y: f32) -> bool {
+            false
+        }
+        fn item_at(&self, _x: f32, _y: f32) -> Option<usize> {
+            None
+        }
+        fn visible_count(&self) -> usize {
+            10
+        }
+    }
+
+    fn list_view(len: usize, selection: Vec<usize>) -> ListView<TestSkin> {
+        let mut list_view = ListViewBuilder::new()
+            .set_items((0..len).map(|n| n.to_string()))
+            .set_selection_mode(SelectionMode::Multi)
+            .set_selection(selection)
+            .build();
+        list_view.state.current = Some(2);
+        list_view.anchor = Some(2);
+        list_view
+    }
+
+    #[test]
+    fn insert_shifts_selection() {
+        let mut list_view = list_view(5, vec![0, 2, 4]);
+        list_view.insert(2, "new".to_string());
+        assert_eq!(list_view.get_items()[2], "new");
+        assert_eq!(list_view.get_selection(), &[0, 3, 5]);
+        assert_eq!(list_view.state.current, Some(3));
+        assert_eq!(list_view.anchor, Some(3));
+        list_view.insert(100, "last".to_string());
+        assert_eq!(list_view.get_items().last().unwrap(), "last");
+        assert_eq!(list_view.get_selection(), &[0, 3, 5]);
+    }
+
+    #[test]
+    fn remove_shifts_selection() {
+        let mut list_view = list_view(5, vec![0, 2, 4]);
+        assert_eq!(list_view.remove(1), Some("1".to_string()));
+        assert_eq!(list_view.get_selection(), &[0, 1, 3]);
+        assert_eq!(list_view.state.current, Some(1));
+        assert_eq!(list_view.remove(1), Some("2".to_string()));
+        assert_eq!(list_view.get_selection(), &[0, 2]);
+        assert_eq!(list_view.state.current, Some(1));
+        assert_eq!(list_view.remove(10), None);
+        assert_eq!(list_view.get_selection(), &[0, 2]);
+    }
+
+    #[test]
+    fn remove_last_item_clears_cursor() {
+        let mut list_view = list_view(3, vec![2]);
+        assert_eq!(list_view.remove(2), Some("2".to_string()));
+        assert!(list_view.get_selection().is_empty());
+        assert_eq!(list_view.state.current, None);
+        assert_eq!(list_view.anchor, None);
+    }
+}
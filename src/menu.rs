@@ -0,0 +1,737 @@
+use crate::button::ButtonMode;
+use crate::overlay::{OverlayId, Placement, PopupOptions};
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, ServiceRegistration, SrvId};
+use async_std::task;
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("A", KeyCode::A),
+    ("B", KeyCode::B),
+    ("C", KeyCode::C),
+    ("D", KeyCode::D),
+    ("E", KeyCode::E),
+    ("F", KeyCode::F),
+    ("G", KeyCode::G),
+    ("H", KeyCode::H),
+    ("I", KeyCode::I),
+    ("J", KeyCode::J),
+    ("K", KeyCode::K),
+    ("L", KeyCode::L),
+    ("M", KeyCode::M),
+    ("N", KeyCode::N),
+    ("O", KeyCode::O),
+    ("P", KeyCode::P),
+    ("Q", KeyCode::Q),
+    ("R", KeyCode::R),
+    ("S", KeyCode::S),
+    ("T", KeyCode::T),
+    ("U", KeyCode::U),
+    ("V", KeyCode::V),
+    ("W", KeyCode::W),
+    ("X", KeyCode::X),
+    ("Y", KeyCode::Y),
+    ("Z", KeyCode::Z),
+    ("0", KeyCode::Key0),
+    ("1", KeyCode::Key1),
+    ("2", KeyCode::Key2),
+    ("3", KeyCode::Key3),
+    ("4", KeyCode::Key4),
+    ("5", KeyCode::Key5),
+    ("6", KeyCode::Key6),
+    ("7", KeyCode::Key7),
+    ("8", KeyCode::Key8),
+    ("9", KeyCode::Key9),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+    ("Esc", KeyCode::Escape),
+    ("Ins", KeyCode::Insert),
+    ("Del", KeyCode::Delete),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Backspace", KeyCode::Back),
+    ("Enter", KeyCode::Return),
+    ("Space", KeyCode::Space),
+    ("Tab", KeyCode::Tab),
+];
+
+/// Letter or digit typed by the key, used to match mnemonics
+pub(crate) fn key_char(keycode: KeyCode) -> Option<char> {
+    let (name, _) = KEY_NAMES.iter().find(|(_, k)| *k == keycode)?;
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c.to_ascii_lowercase()),
+        _ => None,
+    }
+}
+
+/// Key combination which activates menu item from anywhere in the window
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Accelerator {
+    pub keycode: KeyCode,
+    pub keymods: KeyMods,
+}
+
+impl Accelerator {
+    pub fn new(keycode: KeyCode, keymods: KeyMods) -> Self {
+        Self { keycode, keymods }
+    }
+    /// Parses hints like "Ctrl+S" or "Ctrl+Shift+F5"
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut keymods = KeyMods::NONE;
+        let mut parts: Vec<&str> = s.split('+').map(|p| p.trim()).collect();
+        let key = parts.pop()?;
+        for part in parts {
+            keymods |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyMods::CTRL,
+                "alt" => KeyMods::ALT,
+                "shift" => KeyMods::SHIFT,
+                "logo" | "super" | "cmd" => KeyMods::LOGO,
+                _ => return None,
+            }
+        }
+        let (_, keycode) = KEY_NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))?;
+        Some(Self::new(*keycode, keymods))
+    }
+    pub fn matches(&self, keycode: KeyCode, keymods: KeyMods) -> bool {
+        self.keycode == keycode && self.keymods == keymods
+    }
+}
+
+impl Display for Accelerator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (keymod, name) in &[
+            (KeyMods::CTRL, "Ctrl+"),
+            (KeyMods::ALT, "Alt+"),
+            (KeyMods::SHIFT, "Shift+"),
+            (KeyMods::LOGO, "Logo+"),
+        ] {
+            if self.keymods.contains(*keymod) {
+                write!(f, "{}", name)?;
+            }
+        }
+        match KEY_NAMES.iter().find(|(_, k)| *k == self.keycode) {
+            Some((name, _)) => write!(f, "{}", name),
+            None => write!(f, "{:?}", self.keycode),
+        }
+    }
+}
+
+/// Menu entry: action, checkable item (`ButtonMode::Checkbox`), submenu or separator.
+/// Character after '&' in the label is the mnemonic, e.g. "&File".
+#[derive(Clone, Default, Debug)]
+pub struct MenuItem {
+    pub label: String,
+    pub mode: ButtonMode,
+    /// Not empty for submenu
+    pub items: Vec<MenuItem>,
+    pub separator: bool,
+    pub accelerator: Option<Accelerator>,
+}
+
+impl MenuItem {
+    pub fn new<T: Into<String>>(label: T) -> Self {
+        Self {
+            label: label.into(),
+            ..Self::default()
+        }
+    }
+    pub fn checkbox<T: Into<String>>(label: T, checked: bool) -> Self {
+        Self {
+            label: label.into(),
+            mode: ButtonMode::Checkbox(checked),
+            ..Self::default()
+        }
+    }
+    pub fn submenu<T: Into<String>>(label: T, items: Vec<MenuItem>) -> Self {
+        Self {
+            label: label.into(),
+            items,
+            ..Self::default()
+        }
+    }
+    pub fn separator() -> Self {
+        Self {
+            separator: true,
+            ..Self::default()
+        }
+    }
+    pub fn set_accelerator(mut self, accelerator: Accelerator) -> Self {
+        self.accelerator = Some(accelerator);
+        self
+    }
+    /// Label without mnemonic marker
+    pub fn display_label(&self) -> String {
+        self.label.replacen('&', "", 1)
+    }
+    pub fn mnemonic(&self) -> Option<char> {
+        let index = self.label.find('&')?;
+        self.label[index + 1..]
+            .chars()
+            .next()
+            .map(|c| c.to_ascii_lowercase())
+    }
+    pub fn is_submenu(&self) -> bool {
+        !self.items.is_empty()
+    }
+    /// Item which can be highlighted and activated
+    pub fn is_selectable(&self) -> bool {
+        !self.separator
+    }
+}
+
+/// Finds item by indices of items and submenu items
+pub fn find_item<'a>(items: &'a [MenuItem], path: &[usize]) -> Option<&'a MenuItem> {
+    let (first, rest) = path.split_first()?;
+    let item = items.get(*first)?;
+    if rest.is_empty() {
+        Some(item)
+    } else {
+        find_item(&item.items, rest)
+    }
+}
+
+pub fn find_item_mut<'a>(items: &'a mut [MenuItem], path: &[usize]) -> Option<&'a mut MenuItem> {
+    let (first, rest) = path.split_first()?;
+    let item = items.get_mut(*first)?;
+    if rest.is_empty() {
+        Some(item)
+    } else {
+        find_item_mut(&mut item.items, rest)
+    }
+}
+
+/// Path to the item with the accelerator
+pub fn find_accelerator(
+    items: &[MenuItem],
+    keycode: KeyCode,
+    keymods: KeyMods,
+) -> Option<Vec<usize>> {
+    items.iter().enumerate().find_map(|(index, item)| {
+        if item
+            .accelerator
+            .is_some_and(|a| a.matches(keycode, keymods))
+        {
+            Some(vec![index])
+        } else {
+            find_accelerator(&item.items, keycode, keymods).map(|mut path| {
+                path.insert(0, index);
+                path
+            })
+        }
+    })
+}
+
+/// Toggles checkable item and describes the activation
+pub(crate) fn activate_item(items: &mut [MenuItem], path: Vec<usize>) -> Option<MenuActivation> {
+    let item = find_item_mut(items, &path)?;
+    if item.is_submenu() || !item.is_selectable() {
+        return None;
+    }
+    if let ButtonMode::Checkbox(checked) = item.mode {
+        item.mode = ButtonMode::Checkbox(!checked)
+    }
+    Some(MenuActivation {
+        path,
+        item: item.clone(),
+    })
+}
+
+/// Activated item with the path to it. For checkable item the state is already toggled.
+#[derive(Clone, Debug)]
+pub struct MenuActivation {
+    pub path: Vec<usize>,
+    pub item: MenuItem,
+}
+
+pub(crate) type ActivateHandler = Box<dyn Fn(&mut dyn Widget, &MenuActivation) + Send + Sync>;
+
+#[derive(Clone, Default, Debug)]
+pub struct MenuState {
+    pub items: Vec<MenuItem>,
+    pub highlighted: Option<usize>,
+    pub rect: Rect,
+}
+
+pub trait MenuSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &MenuState);
+    fn item_at(&self, x: f32, y: f32) -> Option<usize>;
+    fn item_rect(&self, index: usize) -> Rect;
+    /// Size of the popup for given items
+    fn menu_size(&self, items: &[MenuItem]) -> (f32, f32);
+}
+
+type OverlayRequest = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Overlay requests of one menu chain. They are served in order by a single
+/// task, so a popup is never closed before it's opened.
+#[derive(Default)]
+struct OverlayQueue {
+    requests: VecDeque<OverlayRequest>,
+    running: bool,
+}
+
+/// Popups of one open menu: the root one and opened submenus by depth.
+/// Activation is reported by path of item indices.
+#[derive(Clone)]
+pub(crate) struct MenuChain {
+    overlay: OverlayId,
+    popups: Arc<Mutex<Vec<SrvId>>>,
+    queue: Arc<Mutex<OverlayQueue>>,
+    on_activate: Arc<dyn Fn(Vec<usize>) + Send + Sync>,
+    /// Called with `true` for Right and `false` for Left key which
+    /// should switch to the neighbour menu of the menu bar
+    on_navigate: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+}
+
+impl MenuChain {
+    pub(crate) fn new<F: Fn(Vec<usize>) + Send + Sync + 'static>(
+        overlay: OverlayId,
+        on_activate: F,
+    ) -> Self {
+        Self {
+            overlay,
+            popups: Arc::new(Mutex::new(Vec::new())),
+            queue: Arc::new(Mutex::new(OverlayQueue::default())),
+            on_activate: Arc::new(on_activate),
+            on_navigate: None,
+        }
+    }
+    pub(crate) fn set_on_navigate<F: Fn(bool) + Send + Sync + 'static>(&mut self, f: F) {
+        self.on_navigate = Some(Arc::new(f))
+    }
+    /// Opens menu popup with given items at the depth, closing deeper ones
+    pub(crate) fn open<S: MenuSkin + 'static>(
+        &self,
+        depth: usize,
+        path: Vec<usize>,
+        items: Vec<MenuItem>,
+        highlighted: Option<usize>,
+        anchor: Rect,
+        placement: Placement,
+    ) {
+        self.close_from(depth);
+        let popup = MenuPopup::<S>::new(self.clone(), depth, path, items, highlighted);
+        self.popups.lock().unwrap().push(popup.srv_id());
+        let options = PopupOptions::new()
+            .set_anchor(anchor)
+            .set_placement(placement)
+            .set_close_on_outside_click(true)
+            .on_close({
+                let popups = self.popups.clone();
+                move |popup| {
+                    let id = popup.srv_id();
+                    popups.lock().unwrap().retain(|p| *p != id)
+                }
+            });
+        let overlay = self.overlay;
+        self.send(Box::pin(
+            async move { overlay.open_with(popup, options).await },
+        ));
+    }
+    pub(crate) fn close_from(&self, depth: usize) {
+        let closed = {
+            let mut popups = self.popups.lock().unwrap();
+            if depth >= popups.len() {
+                return;
+            }
+            popups.split_off(depth)
+        };
+        let overlay = self.overlay;
+        self.send(Box::pin(async move {
+            for popup in closed.into_iter().rev() {
+                overlay.close(popup).await;
+            }
+        }));
+    }
+    pub(crate) fn depth(&self) -> usize {
+        self.popups.lock().unwrap().len()
+    }
+    fn activate(&self, path: Vec<usize>) {
+        self.close_from(0);
+        (self.on_activate)(path)
+    }
+    /// Queues the request, starting the serving task if it's not running
+    fn send(&self, request: OverlayRequest) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.requests.push_back(request);
+        if queue.running {
+            return;
+        }
+        queue.running = true;
+        let queue = self.queue.clone();
+        task::spawn(async move {
+            loop {
+                let request = {
+                    let mut queue = queue.lock().unwrap();
+                    match queue.requests.pop_front() {
+                        Some(request) => request,
+                        None => {
+                            queue.running = false;
+                            return;
+                        }
+                    }
+                };
+                request.await
+            }
+        });
+    }
+    fn navigate(&self, forward: bool) {
+        if let Some(on_navigate) = &self.on_navigate {
+            on_navigate(forward)
+        }
+    }
+}
+
+/// One level of open menu in the overlay
+pub struct MenuPopup<S: MenuSkin> {
+    state: MenuState,
+    skin: S,
+    reg: ServiceRegistration,
+    chain: MenuChain,
+    depth: usize,
+    path: Vec<usize>,
+    /// Index of the item whose submenu is open
+    open_submenu: Option<usize>,
+}
+
+impl<S: MenuSkin + 'static> MenuPopup<S> {
+    fn new(
+        chain: MenuChain,
+        depth: usize,
+        path: Vec<usize>,
+        items: Vec<MenuItem>,
+        highlighted: Option<usize>,
+    ) -> Self {
+        let skin = S::default();
+        let (w, h) = skin.menu_size(&items);
+        Self {
+            state: MenuState {
+                items,
+                highlighted,
+                rect: Rect::new(0., 0., w, h),
+            },
+            skin,
+            reg: register_service(),
+            chain,
+            depth,
+            path,
+            open_submenu: None,
+        }
+    }
+    fn contains(&self, x: f32, y: f32) -> bool {
+        self.state.rect.contains(Point2::new(x, y))
+    }
+    fn item_path(&self, index: usize) -> Vec<usize> {
+        let mut path = self.path.clone();
+        path.push(index);
+        path
+    }
+    fn open_submenu(&mut self, index: usize, highlight_first: bool) {
+        if self.open_submenu == Some(index) && self.chain.depth() > self.depth + 1 {
+            return;
+        }
+        let items = self.state.items[index].items.clone();
+        let highlighted = if highlight_first {
+            items.iter().position(|item| item.is_selectable())
+        } else {
+            None
+        };
+        self.skin.set_state(&self.state);
+        self.chain.open::<S>(
+            self.depth + 1,
+            self.item_path(index),
+            items,
+            highlighted,
+            self.skin.item_rect(index),
+            Placement::Right,
+        );
+        self.open_submenu = Some(index);
+    }
+    fn close_submenu(&mut self) {
+        if self.open_submenu.take().is_some() {
+            self.chain.close_from(self.depth + 1);
+        }
+    }
+    /// Opens submenu or activates the item
+    fn choose(&mut self, index: usize, by_keyboard: bool) {
+        let item = &self.state.items[index];
+        if !item.is_selectable() {
+            return;
+        }
+        if item.is_submenu() {
+            self.open_submenu(index, by_keyboard)
+        } else {
+            self.chain.activate(self.item_path(index))
+        }
+    }
+    /// Moves highlight to the next selectable item in the direction, wrapping around
+    fn move_highlight(&mut self, forward: bool) {
+        let count = self.state.items.len();
+        if count == 0 {
+            return;
+        }
+        let mut index = self
+            .state
+            .highlighted
+            .unwrap_or(if forward { count - 1 } else { 0 });
+        for _ in 0..count {
+            index = if forward {
+                (index + 1) % count
+            } else {
+                (index + count - 1) % count
+            };
+            if self.state.items[index].is_selectable() {
+                self.state.highlighted = Some(index);
+                return;
+            }
+        }
+    }
+}
+
+impl<S: MenuSkin + 'static> Widget for MenuPopup<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("items", self.state.items.len().to_string()),
+            ("depth", self.depth.to_string()),
+        ]
+    }
+}
+
+impl<S: MenuSkin + 'static> EventHandlerProxy for MenuPopup<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        _button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        self.contains(x, y)
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        _button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if !self.contains(x, y) {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        if let Some(index) = self.skin.item_at(x, y) {
+            self.choose(index, false);
+        }
+        true
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> bool {
+        if !self.contains(x, y) {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        match self.skin.item_at(x, y) {
+            Some(index) if self.state.items[index].is_selectable() => {
+                self.state.highlighted = Some(index);
+                if self.state.items[index].is_submenu() {
+                    self.open_submenu(index, false)
+                } else {
+                    self.close_submenu()
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        match keycode {
+            KeyCode::Up => self.move_highlight(false),
+            KeyCode::Down => self.move_highlight(true),
+            KeyCode::Right => match self.state.highlighted {
+                Some(index) if self.state.items[index].is_submenu() => {
+                    self.open_submenu(index, true)
+                }
+                _ => self.chain.navigate(true),
+            },
+            KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space => {
+                if let Some(index) = self.state.highlighted {
+                    self.choose(index, true)
+                }
+            }
+            KeyCode::Left if self.depth > 0 => self.chain.close_from(self.depth),
+            KeyCode::Left => self.chain.navigate(false),
+            KeyCode::Escape => self.chain.close_from(0),
+            _ if keymods.is_empty() || keymods == KeyMods::SHIFT => {
+                let mnemonic = key_char(keycode);
+                let index = self
+                    .state
+                    .items
+                    .iter()
+                    .position(|item| mnemonic.is_some() && item.mnemonic() == mnemonic);
+                if let Some(index) = index {
+                    self.state.highlighted = Some(index);
+                    self.choose(index, true)
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn key_up_event(&mut self, _ctx: &mut Context, _keycode: KeyCode, _keymods: KeyMods) -> bool {
+        true
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, _character: char) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accelerator_is_parsed() {
+        assert_eq!(
+            Accelerator::parse("Ctrl+S"),
+            Some(Accelerator::new(KeyCode::S, KeyMods::CTRL))
+        );
+        assert_eq!(
+            Accelerator::parse("control + shift + f5"),
+            Some(Accelerator::new(
+                KeyCode::F5,
+                KeyMods::CTRL | KeyMods::SHIFT
+            ))
+        );
+        assert_eq!(
+            Accelerator::parse("Del"),
+            Some(Accelerator::new(KeyCode::Delete, KeyMods::NONE))
+        );
+        assert_eq!(Accelerator::parse(""), None);
+        assert_eq!(Accelerator::parse("Ctrl+"), None);
+        assert_eq!(Accelerator::parse("Hyper+S"), None);
+        assert_eq!(Accelerator::parse("Ctrl+Unknown"), None);
+    }
+
+    #[test]
+    fn accelerator_display_round_trips() {
+        for s in &[
+            "Ctrl+S",
+            "Alt+F4",
+            "Ctrl+Shift+PageDown",
+            "Ctrl+Alt+Shift+Logo+1",
+            "Esc",
+        ] {
+            let accelerator = Accelerator::parse(s).unwrap();
+            assert_eq!(accelerator.to_string(), *s);
+            assert_eq!(
+                Accelerator::parse(&accelerator.to_string()),
+                Some(accelerator)
+            );
+        }
+        // modifiers are always shown in the same order
+        let accelerator = Accelerator::parse("shift+ctrl+z").unwrap();
+        assert_eq!(accelerator.to_string(), "Ctrl+Shift+Z");
+    }
+
+    #[test]
+    fn accelerator_is_found_in_submenus() {
+        let accelerator = |s| Accelerator::parse(s).unwrap();
+        let items = vec![
+            MenuItem::submenu(
+                "&File",
+                vec![
+                    MenuItem::new("&New").set_accelerator(accelerator("Ctrl+N")),
+                    MenuItem::separator(),
+                    MenuItem::submenu(
+                        "&Recent",
+                        vec![MenuItem::new("a.txt").set_accelerator(accelerator("Ctrl+1"))],
+                    ),
+                ],
+            ),
+            MenuItem::submenu(
+                "&Edit",
+                vec![MenuItem::new("&Copy").set_accelerator(accelerator("Ctrl+C"))],
+            ),
+        ];
+        assert_eq!(
+            find_accelerator(&items, KeyCode::N, KeyMods::CTRL),
+            Some(vec![0, 0])
+        );
+        assert_eq!(
+            find_accelerator(&items, KeyCode::Key1, KeyMods::CTRL),
+            Some(vec![0, 2, 0])
+        );
+        assert_eq!(
+            find_accelerator(&items, KeyCode::C, KeyMods::CTRL),
+            Some(vec![1, 0])
+        );
+        assert_eq!(
+            find_accelerator(&items, KeyCode::C, KeyMods::CTRL | KeyMods::SHIFT),
+            None
+        );
+        assert_eq!(find_accelerator(&items, KeyCode::X, KeyMods::CTRL), None);
+    }
+}
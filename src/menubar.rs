@@ -0,0 +1,404 @@
+use crate::menu::{
+    activate_item, find_accelerator, key_char, ActivateHandler, MenuActivation, MenuChain,
+    MenuItem, MenuSkin,
+};
+use crate::overlay::{OverlayId, Placement};
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use async_std::task;
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+#[derive(Clone, Default, Debug)]
+pub struct MenuBarState {
+    /// Top level menus, items without submenu are activated by click
+    pub items: Vec<MenuItem>,
+    /// Menu which drop-down is open
+    pub open: Option<usize>,
+    pub hovered: Option<usize>,
+    pub rect: Rect,
+}
+
+pub trait MenuBarSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &MenuBarState);
+    fn item_at(&self, x: f32, y: f32) -> Option<usize>;
+    /// Title of the menu, drop-down is placed below it
+    fn item_rect(&self, index: usize) -> Rect;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MenuBarId(SrvId);
+
+impl From<MenuBarId> for SrvId {
+    fn from(v: MenuBarId) -> SrvId {
+        v.0
+    }
+}
+
+enum MenuBarOp {
+    GetItems,
+    SetItems(Vec<MenuItem>),
+    OnActivate(ActivateHandler),
+    RemoveOnActivate(usize),
+    Activate(Vec<usize>),
+    Navigate(bool),
+}
+
+impl Debug for MenuBarOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MenuBarOp::GetItems => write!(f, "GetItems"),
+            MenuBarOp::SetItems(items) => write!(f, "SetItems({:?})", items),
+            MenuBarOp::OnActivate(_) => write!(f, "OnActivate"),
+            MenuBarOp::RemoveOnActivate(handler_id) => {
+                write!(f, "RemoveOnActivate({:?})", handler_id)
+            }
+            MenuBarOp::Activate(path) => write!(f, "Activate({:?})", path),
+            MenuBarOp::Navigate(forward) => write!(f, "Navigate({:?})", forward),
+        }
+    }
+}
+
+impl MenuBarId {
+    pub async fn get_items(self) -> Vec<MenuItem> {
+        send_request(self.0, MenuBarOp::GetItems).await.unwrap()
+    }
+    pub async fn set_items(self, items: Vec<MenuItem>) {
+        send_request(self.0, MenuBarOp::SetItems(items))
+            .await
+            .unwrap()
+    }
+    pub async fn on_activate<F: Fn(&mut dyn Widget, &MenuActivation) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, MenuBarOp::OnActivate(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_activate(self, handler_id: usize) {
+        send_request(self.0, MenuBarOp::RemoveOnActivate(handler_id))
+            .await
+            .unwrap()
+    }
+    async fn activate(self, path: Vec<usize>) {
+        send_request(self.0, MenuBarOp::Activate(path))
+            .await
+            .unwrap()
+    }
+    async fn navigate(self, forward: bool) {
+        send_request(self.0, MenuBarOp::Navigate(forward))
+            .await
+            .unwrap()
+    }
+}
+
+/// Row of drop-down menus. Alt with the mnemonic opens the menu, accelerators
+/// of the items are activated from anywhere in the window.
+pub struct MenuBar<B: MenuBarSkin, S: MenuSkin> {
+    state: MenuBarState,
+    skin: B,
+    reg: ServiceRegistration,
+    overlay: Option<OverlayId>,
+    chain: Option<MenuChain>,
+    on_activate_handlers: IndexMap<usize, ActivateHandler>,
+    _menu_skin: PhantomData<S>,
+}
+
+impl<B: MenuBarSkin + 'static, S: MenuSkin + 'static> MenuBar<B, S> {
+    pub fn new() -> Self {
+        Self {
+            state: MenuBarState::default(),
+            skin: B::default(),
+            reg: register_service(),
+            overlay: None,
+            chain: None,
+            on_activate_handlers: IndexMap::new(),
+            _menu_skin: PhantomData,
+        }
+    }
+    pub fn id(&self) -> MenuBarId {
+        MenuBarId(self.reg.id())
+    }
+    pub fn set_items(&mut self, items: Vec<MenuItem>) {
+        self.close_menu();
+        self.state.items = items
+    }
+    pub fn get_items(&self) -> &[MenuItem] {
+        &self.state.items
+    }
+    pub fn add_item(&mut self, item: MenuItem) {
+        self.state.items.push(item)
+    }
+    /// Overlay where the menus are opened. If it's not set, the overlay of the
+    /// `GuiRoot` owning the menu bar is used; without any overlay the menus
+    /// are not opened at all.
+    pub fn set_overlay(&mut self, overlay: Option<OverlayId>) {
+        if overlay != self.overlay {
+            self.close_menu();
+            self.chain = None;
+            self.overlay = overlay
+        }
+    }
+    pub fn get_overlay(&self) -> Option<OverlayId> {
+        self.overlay
+    }
+    pub fn on_activate_box(&mut self, handler: ActivateHandler) -> usize {
+        add_to_indexmap(&mut self.on_activate_handlers, handler)
+    }
+    pub fn on_activate<F: Fn(&mut dyn Widget, &MenuActivation) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_activate_box(Box::new(f))
+    }
+    pub fn remove_on_activate(&mut self, handler_id: usize) {
+        self.on_activate_handlers.remove(&handler_id);
+    }
+    fn chain(&mut self) -> Option<MenuChain> {
+        if self.chain.is_none() {
+            let overlay = self.overlay?;
+            let id = self.id();
+            let mut chain = MenuChain::new(overlay, move |path| {
+                task::spawn(async move { id.activate(path).await });
+            });
+            chain.set_on_navigate(move |forward| {
+                task::spawn(async move { id.navigate(forward).await });
+            });
+            self.chain = Some(chain);
+        }
+        self.chain.clone()
+    }
+    fn open_menu(&mut self, index: usize, highlight_first: bool) {
+        if !self.state.items[index].is_submenu() {
+            self.close_menu();
+            self.activate(vec![index]);
+            return;
+        }
+        let chain = match self.chain() {
+            Some(chain) => chain,
+            None => return,
+        };
+        let items = self.state.items[index].items.clone();
+        let highlighted = if highlight_first {
+            items.iter().position(|item| item.is_selectable())
+        } else {
+            None
+        };
+        self.skin.set_state(&self.state);
+        chain.open::<S>(
+            0,
+            vec![index],
+            items,
+            highlighted,
+            self.skin.item_rect(index),
+            Placement::Below,
+        );
+        self.state.open = Some(index);
+    }
+    fn close_menu(&mut self) {
+        if self.state.open.take().is_some() {
+            if let Some(chain) = &self.chain {
+                chain.close_from(0)
+            }
+        }
+    }
+    fn navigate(&mut self, forward: bool) {
+        let count = self.state.items.len();
+        if let Some(open) = self.state.open {
+            let index = if forward {
+                (open + 1) % count
+            } else {
+                (open + count - 1) % count
+            };
+            if self.state.items[index].is_submenu() {
+                self.open_menu(index, true)
+            }
+        }
+    }
+    fn activate(&mut self, path: Vec<usize>) {
+        let activation = match activate_item(&mut self.state.items, path) {
+            Some(activation) => activation,
+            None => return,
+        };
+        let handlers = std::mem::replace(&mut self.on_activate_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, &activation);
+        }
+        self.on_activate_handlers = handlers;
+    }
+}
+
+impl<B: MenuBarSkin + 'static, S: MenuSkin + 'static> Default for MenuBar<B, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: MenuBarSkin + 'static, S: MenuSkin + 'static> Widget for MenuBar<B, S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("items", self.state.items.len().to_string()),
+            ("open", format!("{:?}", self.state.open)),
+        ]
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.overlay.get_or_insert(overlay);
+    }
+}
+
+impl<B: MenuBarSkin + 'static, S: MenuSkin + 'static> EventHandlerProxy for MenuBar<B, S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            MenuBarOp::GetItems => Some(Box::new(self.get_items().to_vec())),
+            MenuBarOp::SetItems(items) => {
+                self.set_items(items);
+                Some(Box::new(()))
+            }
+            MenuBarOp::OnActivate(handler) => Some(Box::new(self.on_activate_box(handler))),
+            MenuBarOp::RemoveOnActivate(handler_id) => {
+                self.remove_on_activate(handler_id);
+                Some(Box::new(()))
+            }
+            MenuBarOp::Activate(path) => {
+                self.activate(path);
+                Some(Box::new(()))
+            }
+            MenuBarOp::Navigate(forward) => {
+                self.navigate(forward);
+                Some(Box::new(()))
+            }
+        });
+        // drop-down could be closed by outside click or Escape
+        if self.chain.as_ref().is_none_or(|chain| chain.depth() == 0) {
+            self.state.open = None;
+        }
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button != MouseButton::Left || !self.state.rect.contains(Point2::new(x, y)) {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        match self.skin.item_at(x, y) {
+            Some(index) if self.state.open == Some(index) => self.close_menu(),
+            Some(index) => self.open_menu(index, false),
+            None => self.close_menu(),
+        }
+        true
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> bool {
+        self.skin.set_state(&self.state);
+        self.state.hovered = self.skin.item_at(x, y);
+        // while some menu is open hovering another title switches to it
+        match (self.state.open, self.state.hovered) {
+            (Some(open), Some(hovered))
+                if open != hovered && self.state.items[hovered].is_submenu() =>
+            {
+                self.open_menu(hovered, false)
+            }
+            _ => {}
+        }
+        self.state.hovered.is_some()
+    }
+
+    fn shortcut_event(&mut self, _ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        if keymods == KeyMods::ALT {
+            let mnemonic = key_char(keycode);
+            let index = self
+                .state
+                .items
+                .iter()
+                .position(|item| mnemonic.is_some() && item.mnemonic() == mnemonic);
+            if let Some(index) = index {
+                self.open_menu(index, true);
+                return true;
+            }
+        }
+        match find_accelerator(&self.state.items, keycode, keymods) {
+            Some(path) => {
+                self.close_menu();
+                self.activate(path);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub struct MenuBarBuilder<B: MenuBarSkin, S: MenuSkin> {
+    menu_bar: MenuBar<B, S>,
+}
+
+impl<B: MenuBarSkin + 'static, S: MenuSkin + 'static> Default for MenuBarBuilder<B, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: MenuBarSkin + 'static, S: MenuSkin + 'static> MenuBarBuilder<B, S> {
+    pub fn new() -> Self {
+        Self {
+            menu_bar: MenuBar::new(),
+        }
+    }
+    /// Adds top level menu
+    pub fn add_menu<T: Into<String>>(mut self, label: T, items: Vec<MenuItem>) -> Self {
+        self.menu_bar.add_item(MenuItem::submenu(label, items));
+        self
+    }
+    pub fn add_item(mut self, item: MenuItem) -> Self {
+        self.menu_bar.add_item(item);
+        self
+    }
+    pub fn set_overlay(mut self, overlay: OverlayId) -> Self {
+        self.menu_bar.set_overlay(Some(overlay));
+        self
+    }
+    pub fn on_activate<F: Fn(&mut dyn Widget, &MenuActivation) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.menu_bar.on_activate(f);
+        self
+    }
+    pub fn build(self) -> MenuBar<B, S> {
+        self.menu_bar
+    }
+}
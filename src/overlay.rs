@@ -0,0 +1,491 @@
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::{self, Color, DrawMode, DrawParam, MeshBuilder, Rect};
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+use std::fmt::{Debug, Formatter};
+
+/// Dims everything below modal popup
+const MODAL_BACKDROP: Color = Color::new(0., 0., 0., 0.5);
+
+/// Side of the anchor rect where the popup is placed. If the popup doesn't
+/// fit into the overlay there it's flipped to the opposite side.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum Placement {
+    #[default]
+    Below,
+    Above,
+    Right,
+    Left,
+    /// Top left corners of popup and anchor are matched
+    Over,
+    /// Centered in the anchor, or in the overlay if there is no anchor
+    Center,
+}
+
+/// Popup rect of given size placed near the anchor and moved inside the bounds
+pub fn place_popup(w: f32, h: f32, anchor: Rect, placement: Placement, bounds: Rect) -> Rect {
+    let (x, y) = match placement {
+        Placement::Below if anchor.bottom() + h > bounds.bottom() && anchor.y - h >= bounds.y => {
+            (anchor.x, anchor.y - h)
+        }
+        Placement::Below => (anchor.x, anchor.bottom()),
+        Placement::Above if anchor.y - h < bounds.y && anchor.bottom() + h <= bounds.bottom() => {
+            (anchor.x, anchor.bottom())
+        }
+        Placement::Above => (anchor.x, anchor.y - h),
+        Placement::Right if anchor.right() + w > bounds.right() && anchor.x - w >= bounds.x => {
+            (anchor.x - w, anchor.y)
+        }
+        Placement::Right => (anchor.right(), anchor.y),
+        Placement::Left if anchor.x - w < bounds.x && anchor.right() + w <= bounds.right() => {
+            (anchor.right(), anchor.y)
+        }
+        Placement::Left => (anchor.x - w, anchor.y),
+        Placement::Over => (anchor.x, anchor.y),
+        Placement::Center => (
+            anchor.x + (anchor.w - w) / 2.,
+            anchor.y + (anchor.h - h) / 2.,
+        ),
+    };
+    let x = x.min(bounds.right() - w).max(bounds.x);
+    let y = y.min(bounds.bottom() - h).max(bounds.y);
+    Rect::new(x, y, w, h)
+}
+
+type CloseHandler = Box<dyn FnOnce(&mut dyn Widget) + Send + Sync>;
+
+#[derive(Default)]
+pub struct PopupOptions {
+    anchor: Option<Rect>,
+    placement: Placement,
+    z_order: i32,
+    close_on_outside_click: bool,
+    modal: bool,
+    on_close: Option<CloseHandler>,
+}
+
+impl Debug for PopupOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PopupOptions")
+            .field("anchor", &self.anchor)
+            .field("placement", &self.placement)
+            .field("z_order", &self.z_order)
+            .field("close_on_outside_click", &self.close_on_outside_click)
+            .field("modal", &self.modal)
+            .finish()
+    }
+}
+
+impl PopupOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Without anchor the popup keeps its own rect, only moved inside the overlay
+    pub fn set_anchor(mut self, anchor: Rect) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+    pub fn set_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+    /// Popups with bigger z order are drawn above and get events first
+    pub fn set_z_order(mut self, z_order: i32) -> Self {
+        self.z_order = z_order;
+        self
+    }
+    /// Mouse press outside the popup closes it and is consumed
+    pub fn set_close_on_outside_click(mut self, close: bool) -> Self {
+        self.close_on_outside_click = close;
+        self
+    }
+    /// Modal popup consumes all events not consumed by popups above it,
+    /// so the rest of the GUI doesn't get input until the popup is closed
+    pub fn set_modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+    /// Called when the popup is removed from the overlay for any reason
+    pub fn on_close<F: FnOnce(&mut dyn Widget) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.on_close = Some(Box::new(f));
+        self
+    }
+}
+
+struct Popup {
+    widget: Box<dyn Widget>,
+    options: PopupOptions,
+    /// Size requested by the popup when opened
+    size: (f32, f32),
+}
+
+impl Popup {
+    fn contains(&self, x: f32, y: f32) -> bool {
+        match self.widget.get_rect() {
+            Some(rect) => rect.contains(Point2::new(x, y)),
+            None => false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OverlayId(SrvId);
+
+impl From<OverlayId> for SrvId {
+    fn from(v: OverlayId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum OverlayOp {
+    Open(Box<dyn Widget>, PopupOptions),
+    Close(SrvId),
+    CloseAll,
+    BringToFront(SrvId),
+    IsOpen(SrvId),
+}
+
+impl OverlayId {
+    /// Opens popup with default options at its own rect
+    pub async fn open(self, popup: impl Widget + 'static) {
+        self.open_with(popup, PopupOptions::default()).await
+    }
+    /// Popup's rect size is used as its size, position is set by the overlay
+    pub async fn open_with(self, popup: impl Widget + 'static, options: PopupOptions) {
+        send_request(self.0, OverlayOp::Open(Box::new(popup), options))
+            .await
+            .unwrap()
+    }
+    /// Returns false if there is no such popup
+    pub async fn close(self, popup: SrvId) -> bool {
+        send_request(self.0, OverlayOp::Close(popup)).await.unwrap()
+    }
+    pub async fn close_all(self) {
+        send_request(self.0, OverlayOp::CloseAll).await.unwrap()
+    }
+    /// Moves popup above others with the same z order
+    pub async fn bring_to_front(self, popup: SrvId) -> bool {
+        send_request(self.0, OverlayOp::BringToFront(popup))
+            .await
+            .unwrap()
+    }
+    pub async fn is_open(self, popup: SrvId) -> bool {
+        send_request(self.0, OverlayOp::IsOpen(popup))
+            .await
+            .unwrap()
+    }
+}
+
+/// Layer of popups above the root widget. Popups are ordered by z order and then
+/// by opening time; they are drawn after the root and the topmost gets events first.
+pub struct Overlay {
+    popups: Vec<Popup>,
+    rect: Rect,
+    reg: ServiceRegistration,
+}
+
+impl Overlay {
+    pub fn new() -> Self {
+        Self {
+            popups: Vec::new(),
+            rect: Rect::default(),
+            reg: register_service(),
+        }
+    }
+    pub fn id(&self) -> OverlayId {
+        OverlayId(self.reg.id())
+    }
+    pub fn open_box(&mut self, mut widget: Box<dyn Widget>, options: PopupOptions) {
+        widget.attach_overlay(self.id());
+        let size = widget.get_rect().map_or((0., 0.), |rect| (rect.w, rect.h));
+        let mut popup = Popup {
+            widget,
+            options,
+            size,
+        };
+        self.place(&mut popup);
+        let z_order = popup.options.z_order;
+        let index = self
+            .popups
+            .iter()
+            .position(|p| p.options.z_order > z_order)
+            .unwrap_or(self.popups.len());
+        self.popups.insert(index, popup);
+    }
+    pub fn open(&mut self, popup: impl Widget + 'static, options: PopupOptions) {
+        self.open_box(Box::new(popup), options)
+    }
+    pub fn close(&mut self, popup: SrvId) -> Option<Box<dyn Widget>> {
+        let index = self.index_of(popup)?;
+        Some(self.close_index(index))
+    }
+    pub fn close_all(&mut self) {
+        while !self.popups.is_empty() {
+            self.close_index(self.popups.len() - 1);
+        }
+    }
+    pub fn bring_to_front(&mut self, popup: SrvId) -> bool {
+        match self.index_of(popup) {
+            Some(index) => {
+                let popup = self.popups.remove(index);
+                let z_order = popup.options.z_order;
+                let index = self
+                    .popups
+                    .iter()
+                    .position(|p| p.options.z_order > z_order)
+                    .unwrap_or(self.popups.len());
+                self.popups.insert(index, popup);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn is_open(&self, popup: SrvId) -> bool {
+        self.index_of(popup).is_some()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.popups.is_empty()
+    }
+    pub fn is_modal(&self) -> bool {
+        self.popups.iter().any(|p| p.options.modal)
+    }
+    fn index_of(&self, popup: SrvId) -> Option<usize> {
+        self.popups.iter().position(|p| p.widget.srv_id() == popup)
+    }
+    /// Passes event to popups from the top until it's consumed or blocked by modal popup
+    fn dispatch<F: FnMut(&mut dyn Widget) -> bool>(&mut self, mut f: F) -> bool {
+        self.popups
+            .iter_mut()
+            .rev()
+            .any(|p| f(p.widget.as_mut()) || p.options.modal)
+    }
+    fn close_index(&mut self, index: usize) -> Box<dyn Widget> {
+        let mut popup = self.popups.remove(index);
+        if let Some(on_close) = popup.options.on_close.take() {
+            on_close(popup.widget.as_mut());
+        }
+        popup.widget
+    }
+    fn place(&self, popup: &mut Popup) {
+        let (w, h) = popup.size;
+        let rect = match (popup.options.anchor, popup.widget.get_rect()) {
+            (Some(anchor), _) => place_popup(w, h, anchor, popup.options.placement, self.rect),
+            (None, _) if popup.options.placement == Placement::Center => {
+                place_popup(w, h, self.rect, Placement::Center, self.rect)
+            }
+            (None, Some(rect)) => place_popup(w, h, rect, Placement::Over, self.rect),
+            (None, None) => return,
+        };
+        popup.widget.set_rect(rect);
+    }
+}
+
+impl Default for Overlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Overlay {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        let mut popups = std::mem::take(&mut self.popups);
+        for popup in &mut popups {
+            self.place(popup);
+        }
+        self.popups = popups;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.rect)
+    }
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.popups
+            .iter()
+            .map(|p| p.widget.as_ref() as &dyn Widget)
+            .collect()
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.popups
+            .iter_mut()
+            .map(|p| p.widget.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![("popups", self.popups.len().to_string())]
+    }
+    /// Popups get the overlay they are opened in
+    fn attach_overlay(&mut self, _overlay: OverlayId) {}
+}
+
+impl EventHandlerProxy for Overlay {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            OverlayOp::Open(popup, options) => {
+                self.open_box(popup, options);
+                Some(Box::new(()))
+            }
+            OverlayOp::Close(popup) => Some(Box::new(self.close(popup).is_some())),
+            OverlayOp::CloseAll => {
+                self.close_all();
+                Some(Box::new(()))
+            }
+            OverlayOp::BringToFront(popup) => Some(Box::new(self.bring_to_front(popup))),
+            OverlayOp::IsOpen(popup) => Some(Box::new(self.is_open(popup))),
+        });
+        for p in &mut self.popups {
+            p.widget.update(ctx)?
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        for p in &mut self.popups {
+            if p.options.modal {
+                let backdrop = MeshBuilder::new()
+                    .rectangle(DrawMode::fill(), self.rect, MODAL_BACKDROP)
+                    .build(ctx)?;
+                graphics::draw(ctx, &backdrop, DrawParam::default())?;
+            }
+            p.widget.draw(ctx)?
+        }
+        Ok(())
+    }
+
+    // topmost popup under the cursor gets the press, popups above it which
+    // close on outside click are closed and the press is consumed
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        let mut index = self.popups.len();
+        while index > 0 {
+            index -= 1;
+            let popup = &mut self.popups[index];
+            if popup.contains(x, y) || popup.options.modal {
+                let modal = popup.options.modal;
+                return popup.widget.mouse_button_down_event(ctx, button, x, y)
+                    || consumed
+                    || modal;
+            }
+            if popup.options.close_on_outside_click {
+                self.close_index(index);
+                consumed = true;
+            } else if !consumed && popup.widget.mouse_button_down_event(ctx, button, x, y) {
+                return true;
+            }
+        }
+        consumed
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        self.dispatch(|w| w.mouse_button_up_event(ctx, button, x, y))
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        self.dispatch(|w| w.mouse_motion_event(ctx, x, y, dx, dy))
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        self.dispatch(|w| w.mouse_wheel_event(ctx, x, y))
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        self.dispatch(|w| w.key_down_event(ctx, keycode, keymods, repeat))
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        self.dispatch(|w| w.key_up_event(ctx, keycode, keymods))
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        self.dispatch(|w| w.text_input_event(ctx, character))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: Rect = Rect::new(0., 0., 100., 100.);
+
+    fn place(anchor: Rect, placement: Placement) -> (f32, f32) {
+        let rect = place_popup(30., 20., anchor, placement, BOUNDS);
+        assert_eq!((rect.w, rect.h), (30., 20.));
+        (rect.x, rect.y)
+    }
+
+    #[test]
+    fn popup_is_placed_at_requested_side() {
+        let anchor = Rect::new(40., 40., 20., 10.);
+        assert_eq!(place(anchor, Placement::Below), (40., 50.));
+        assert_eq!(place(anchor, Placement::Above), (40., 20.));
+        assert_eq!(place(anchor, Placement::Right), (60., 40.));
+        assert_eq!(place(anchor, Placement::Left), (10., 40.));
+        assert_eq!(place(anchor, Placement::Over), (40., 40.));
+        assert_eq!(place(anchor, Placement::Center), (35., 35.));
+    }
+
+    #[test]
+    fn popup_is_flipped_when_it_does_not_fit() {
+        assert_eq!(
+            place(Rect::new(10., 85., 20., 10.), Placement::Below),
+            (10., 65.)
+        );
+        assert_eq!(
+            place(Rect::new(10., 5., 20., 10.), Placement::Above),
+            (10., 15.)
+        );
+        assert_eq!(
+            place(Rect::new(80., 10., 10., 10.), Placement::Right),
+            (50., 10.)
+        );
+        assert_eq!(
+            place(Rect::new(10., 10., 10., 10.), Placement::Left),
+            (20., 10.)
+        );
+    }
+
+    #[test]
+    fn popup_is_moved_inside_bounds() {
+        // no room on either side, so it's not flipped but shifted
+        assert_eq!(
+            place(Rect::new(90., 10., 10., 85.), Placement::Below),
+            (70., 80.)
+        );
+        assert_eq!(
+            place(Rect::new(-20., -20., 10., 10.), Placement::Over),
+            (0., 0.)
+        );
+        let rect = place_popup(
+            150.,
+            20.,
+            Rect::new(10., 10., 10., 10.),
+            Placement::Below,
+            BOUNDS,
+        );
+        assert_eq!((rect.x, rect.y), (0., 20.));
+    }
+}
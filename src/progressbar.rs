@@ -0,0 +1,170 @@
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::graphics::Rect;
+use ggez::{timer, Context, GameResult};
+use std::fmt::Debug;
+
+/// Seconds for one run of indeterminate progress animation
+const INDETERMINATE_PERIOD: f32 = 1.5;
+
+#[derive(Clone, Debug)]
+pub struct ProgressBarState {
+    /// Progress from 0 to 1, `None` for indeterminate mode
+    pub fraction: Option<f32>,
+    /// Animation phase from 0 to 1 for indeterminate mode
+    pub phase: f32,
+    pub rect: Rect,
+}
+
+impl Default for ProgressBarState {
+    fn default() -> Self {
+        Self {
+            fraction: Some(0.),
+            phase: 0.,
+            rect: Rect::default(),
+        }
+    }
+}
+
+pub trait ProgressBarSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &ProgressBarState);
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ProgressBarId(SrvId);
+
+impl From<ProgressBarId> for SrvId {
+    fn from(v: ProgressBarId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum ProgressBarOp {
+    GetFraction,
+    SetFraction(Option<f32>),
+}
+
+impl ProgressBarId {
+    /// Returns `None` in indeterminate mode
+    pub async fn get_fraction(self) -> Option<f32> {
+        send_request(self.0, ProgressBarOp::GetFraction)
+            .await
+            .unwrap()
+    }
+    pub async fn set_fraction(self, fraction: f32) {
+        send_request(self.0, ProgressBarOp::SetFraction(Some(fraction)))
+            .await
+            .unwrap()
+    }
+    pub async fn set_indeterminate(self) {
+        send_request(self.0, ProgressBarOp::SetFraction(None))
+            .await
+            .unwrap()
+    }
+}
+
+pub struct ProgressBar<S: ProgressBarSkin> {
+    state: ProgressBarState,
+    skin: S,
+    reg: ServiceRegistration,
+}
+
+impl<S: ProgressBarSkin> ProgressBar<S> {
+    pub fn new() -> Self {
+        Self {
+            state: ProgressBarState::default(),
+            skin: S::default(),
+            reg: register_service(),
+        }
+    }
+    pub fn id(&self) -> ProgressBarId {
+        ProgressBarId(self.reg.id())
+    }
+    pub fn set_fraction(&mut self, fraction: f32) {
+        self.state.fraction = Some(fraction.clamp(0., 1.))
+    }
+    pub fn set_indeterminate(&mut self) {
+        self.state.fraction = None
+    }
+    pub fn get_fraction(&self) -> Option<f32> {
+        self.state.fraction
+    }
+}
+
+impl<S: ProgressBarSkin> Default for ProgressBar<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ProgressBarSkin + 'static> Widget for ProgressBar<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![("fraction", format!("{:?}", self.state.fraction))]
+    }
+}
+
+impl<S: ProgressBarSkin + 'static> EventHandlerProxy for ProgressBar<S> {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            ProgressBarOp::GetFraction => Some(Box::new(self.get_fraction())),
+            ProgressBarOp::SetFraction(Some(fraction)) => {
+                self.set_fraction(fraction);
+                Some(Box::new(()))
+            }
+            ProgressBarOp::SetFraction(None) => {
+                self.set_indeterminate();
+                Some(Box::new(()))
+            }
+        });
+        if self.state.fraction.is_none() {
+            let dt = timer::delta(ctx).as_secs_f32();
+            self.state.phase = (self.state.phase + dt / INDETERMINATE_PERIOD).fract();
+        }
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+}
+
+pub struct ProgressBarBuilder<S: ProgressBarSkin> {
+    progress_bar: ProgressBar<S>,
+}
+
+impl<S: ProgressBarSkin> Default for ProgressBarBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ProgressBarSkin> ProgressBarBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            progress_bar: ProgressBar::new(),
+        }
+    }
+    pub fn set_fraction(mut self, fraction: f32) -> Self {
+        self.progress_bar.set_fraction(fraction);
+        self
+    }
+    pub fn set_indeterminate(mut self) -> Self {
+        self.progress_bar.set_indeterminate();
+        self
+    }
+    pub fn build(self) -> ProgressBar<S> {
+        self.progress_bar
+    }
+}
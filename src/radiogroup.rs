@@ -97,16 +97,32 @@ impl EventHandlerProxy for RadioGroup {
     }
 }
 
+impl Default for RadioGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Widget for RadioGroup {
     fn srv_id(&self) -> SrvId {
         self.reg.id()
     }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        let radios = self.state.lock().unwrap().buttons.len();
+        vec![("radios", radios.to_string())]
+    }
 }
 
 pub struct RadioGroupBuilder {
     radio_group: RadioGroup,
 }
 
+impl Default for RadioGroupBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RadioGroupBuilder {
     pub fn new() -> Self {
         RadioGroupBuilder {
@@ -1,14 +1,17 @@
+use crate::overlay::OverlayId;
 use crate::ribbon::RibbonOp::{AddWidget, GetOrientation, RemoveWidget, SetOrientation};
 use crate::ribbon::RibbonOrientation::{Horizontal, Vertical};
 use crate::{EventHandlerProxy, Widget};
 use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
 use ggez::event::MouseButton;
 use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
 use ggez::{Context, GameResult};
 use std::ops::Not;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub enum RibbonOrientation {
+    #[default]
     Horizontal,
     Vertical,
 }
@@ -27,6 +30,7 @@ pub struct Ribbon {
     widgets: Vec<Box<dyn Widget>>,
     rect: Rect,
     orientation: RibbonOrientation,
+    overlay: Option<OverlayId>,
     reg: ServiceRegistration,
 }
 
@@ -66,6 +70,7 @@ impl Ribbon {
             widgets: Vec::new(),
             rect: Rect::default(),
             orientation: RibbonOrientation::Horizontal,
+            overlay: None,
             reg: register_service(),
         }
     }
@@ -76,7 +81,10 @@ impl Ribbon {
     pub fn get_orientation(&self) -> RibbonOrientation {
         self.orientation
     }
-    pub fn add_widget_box(&mut self, widget: Box<dyn Widget>) {
+    pub fn add_widget_box(&mut self, mut widget: Box<dyn Widget>) {
+        if let Some(overlay) = self.overlay {
+            widget.attach_overlay(overlay)
+        }
         self.widgets.push(widget);
         self.update_widgets_rects();
     }
@@ -122,6 +130,12 @@ impl Ribbon {
     }
 }
 
+impl Default for Ribbon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Widget for Ribbon {
     fn srv_id(&self) -> SrvId {
         self.reg.id()
@@ -133,6 +147,27 @@ impl Widget for Ribbon {
     fn get_rect(&self) -> Option<Rect> {
         Some(self.rect)
     }
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.widgets
+            .iter()
+            .map(|w| w.as_ref() as &dyn Widget)
+            .collect()
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.widgets
+            .iter_mut()
+            .map(|w| w.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![("orientation", format!("{:?}", self.orientation))]
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.overlay = Some(overlay);
+        for w in &mut self.widgets {
+            w.attach_overlay(overlay)
+        }
+    }
 }
 
 impl EventHandlerProxy for Ribbon {
@@ -166,16 +201,78 @@ impl EventHandlerProxy for Ribbon {
         Ok(())
     }
 
-    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        for w in &mut self.widgets {
+            consumed |= w.mouse_button_down_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        for w in &mut self.widgets {
+            consumed |= w.mouse_button_up_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        let mut consumed = false;
         for w in &mut self.widgets {
-            w.mouse_button_down_event(ctx, button, x, y)
+            consumed |= w.mouse_motion_event(ctx, x, y, dx, dy)
         }
+        consumed
     }
 
-    fn mouse_button_up_event(&mut self, ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        let mut consumed = false;
         for w in &mut self.widgets {
-            w.mouse_button_up_event(ctx, button, x, y)
+            consumed |= w.mouse_wheel_event(ctx, x, y)
         }
+        consumed
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        let mut consumed = false;
+        for w in &mut self.widgets {
+            consumed |= w.key_down_event(ctx, keycode, keymods, repeat)
+        }
+        consumed
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        let mut consumed = false;
+        for w in &mut self.widgets {
+            consumed |= w.key_up_event(ctx, keycode, keymods)
+        }
+        consumed
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        let mut consumed = false;
+        for w in &mut self.widgets {
+            consumed |= w.text_input_event(ctx, character)
+        }
+        consumed
     }
 }
 
@@ -183,6 +280,12 @@ pub struct RibbonBuilder {
     ribbon: Ribbon,
 }
 
+impl Default for RibbonBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RibbonBuilder {
     pub fn new() -> Self {
         Self {
@@ -0,0 +1,412 @@
+use crate::ribbon::RibbonOrientation;
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::{Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+
+/// Number of keyboard steps in the range when step is not set
+const CONTINUOUS_KEY_STEPS: f32 = 100.;
+const PAGE_STEPS: f32 = 10.;
+
+#[derive(Clone, Debug)]
+pub struct SliderState {
+    pub orientation: RibbonOrientation,
+    pub min: f32,
+    pub max: f32,
+    /// Zero step means continuous value
+    pub step: f32,
+    pub value: f32,
+    pub dragging: bool,
+    pub focused: bool,
+    pub rect: Rect,
+}
+
+impl Default for SliderState {
+    fn default() -> Self {
+        Self {
+            orientation: RibbonOrientation::Horizontal,
+            min: 0.,
+            max: 1.,
+            step: 0.,
+            value: 0.,
+            dragging: false,
+            focused: false,
+            rect: Rect::default(),
+        }
+    }
+}
+
+impl SliderState {
+    /// Position of the value in the range from 0 to 1
+    pub fn fraction(&self) -> f32 {
+        if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.
+        }
+    }
+    fn snap(&self, value: f32) -> f32 {
+        let value = value.max(self.min).min(self.max);
+        if self.step > 0. {
+            let snapped = self.min + ((value - self.min) / self.step).round() * self.step;
+            snapped.max(self.min).min(self.max)
+        } else {
+            value
+        }
+    }
+    fn key_step(&self) -> f32 {
+        if self.step > 0. {
+            self.step
+        } else {
+            (self.max - self.min) / CONTINUOUS_KEY_STEPS
+        }
+    }
+}
+
+pub trait SliderSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &SliderState);
+    fn is_hot_area(&self, x: f32, y: f32) -> bool;
+    fn is_thumb_area(&self, x: f32, y: f32) -> bool;
+    /// Position on the track from 0 (min) to 1 (max) corresponding to the point
+    fn fraction_at(&self, x: f32, y: f32) -> f32;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SliderId(SrvId);
+
+impl From<SliderId> for SrvId {
+    fn from(v: SliderId) -> SrvId {
+        v.0
+    }
+}
+
+type ValueHandler = Box<dyn Fn(&mut dyn Widget, f32) + Send + Sync>;
+
+enum SliderOp {
+    GetValue,
+    SetValue(f32),
+    GetRange,
+    SetRange(f32, f32),
+    SetStep(f32),
+    OnValueChanged(ValueHandler),
+    RemoveOnValueChanged(usize),
+}
+
+impl Debug for SliderOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SliderOp::GetValue => write!(f, "GetValue"),
+            SliderOp::SetValue(value) => write!(f, "SetValue({:?})", value),
+            SliderOp::GetRange => write!(f, "GetRange"),
+            SliderOp::SetRange(min, max) => write!(f, "SetRange({:?}, {:?})", min, max),
+            SliderOp::SetStep(step) => write!(f, "SetStep({:?})", step),
+            SliderOp::OnValueChanged(_) => write!(f, "OnValueChanged"),
+            SliderOp::RemoveOnValueChanged(handler_id) => {
+                write!(f, "RemoveOnValueChanged({:?})", handler_id)
+            }
+        }
+    }
+}
+
+impl SliderId {
+    pub async fn get_value(self) -> f32 {
+        send_request(self.0, SliderOp::GetValue).await.unwrap()
+    }
+    pub async fn set_value(self, value: f32) {
+        send_request(self.0, SliderOp::SetValue(value))
+            .await
+            .unwrap()
+    }
+    pub async fn get_range(self) -> (f32, f32) {
+        send_request(self.0, SliderOp::GetRange).await.unwrap()
+    }
+    pub async fn set_range(self, min: f32, max: f32) {
+        send_request(self.0, SliderOp::SetRange(min, max))
+            .await
+            .unwrap()
+    }
+    pub async fn set_step(self, step: f32) {
+        send_request(self.0, SliderOp::SetStep(step)).await.unwrap()
+    }
+    pub async fn on_value_changed<F: Fn(&mut dyn Widget, f32) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, SliderOp::OnValueChanged(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_value_changed(self, handler_id: usize) {
+        send_request(self.0, SliderOp::RemoveOnValueChanged(handler_id))
+            .await
+            .unwrap()
+    }
+}
+
+pub struct Slider<S: SliderSkin> {
+    state: SliderState,
+    skin: S,
+    reg: ServiceRegistration,
+    drag_offset: f32,
+    on_value_changed_handlers: IndexMap<usize, ValueHandler>,
+}
+
+impl<S: SliderSkin> Slider<S> {
+    pub fn new() -> Self {
+        Self {
+            state: SliderState::default(),
+            skin: S::default(),
+            reg: register_service(),
+            drag_offset: 0.,
+            on_value_changed_handlers: IndexMap::new(),
+        }
+    }
+    pub fn id(&self) -> SliderId {
+        SliderId(self.reg.id())
+    }
+    pub fn set_orientation(&mut self, orientation: RibbonOrientation) {
+        self.state.orientation = orientation
+    }
+    pub fn get_orientation(&self) -> RibbonOrientation {
+        self.state.orientation
+    }
+    pub fn set_value(&mut self, value: f32) {
+        self.state.value = self.state.snap(value)
+    }
+    pub fn get_value(&self) -> f32 {
+        self.state.value
+    }
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        self.state.min = min;
+        self.state.max = max.max(min);
+        self.set_value(self.state.value)
+    }
+    pub fn get_range(&self) -> (f32, f32) {
+        (self.state.min, self.state.max)
+    }
+    pub fn set_step(&mut self, step: f32) {
+        self.state.step = step.max(0.);
+        self.set_value(self.state.value)
+    }
+    pub fn get_step(&self) -> f32 {
+        self.state.step
+    }
+    pub fn on_value_changed_box(&mut self, handler: ValueHandler) -> usize {
+        add_to_indexmap(&mut self.on_value_changed_handlers, handler)
+    }
+    pub fn on_value_changed<F: Fn(&mut dyn Widget, f32) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_value_changed_box(Box::new(f))
+    }
+    pub fn remove_on_value_changed(&mut self, handler_id: usize) {
+        self.on_value_changed_handlers.remove(&handler_id);
+    }
+}
+
+impl<S: SliderSkin> Default for Slider<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: SliderSkin + 'static> Slider<S> {
+    /// Sets value from user input and notifies handlers if it was changed
+    fn change_value(&mut self, value: f32) {
+        let value = self.state.snap(value);
+        if value != self.state.value {
+            self.state.value = value;
+            let handlers = std::mem::replace(&mut self.on_value_changed_handlers, IndexMap::new());
+            for (_, handler) in &handlers {
+                handler(self, value);
+            }
+            self.on_value_changed_handlers = handlers;
+        }
+    }
+    fn value_at_fraction(&self, fraction: f32) -> f32 {
+        self.state.min + fraction.clamp(0., 1.) * (self.state.max - self.state.min)
+    }
+}
+
+impl<S: SliderSkin + 'static> Widget for Slider<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("orientation", format!("{:?}", self.state.orientation)),
+            ("value", self.state.value.to_string()),
+            ("range", format!("{}..{}", self.state.min, self.state.max)),
+        ]
+    }
+}
+
+impl<S: SliderSkin + 'static> EventHandlerProxy for Slider<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            SliderOp::GetValue => Some(Box::new(self.get_value())),
+            SliderOp::SetValue(value) => {
+                self.set_value(value);
+                Some(Box::new(()))
+            }
+            SliderOp::GetRange => Some(Box::new(self.get_range())),
+            SliderOp::SetRange(min, max) => {
+                self.set_range(min, max);
+                Some(Box::new(()))
+            }
+            SliderOp::SetStep(step) => {
+                self.set_step(step);
+                Some(Box::new(()))
+            }
+            SliderOp::OnValueChanged(handler) => Some(Box::new(self.on_value_changed_box(handler))),
+            SliderOp::RemoveOnValueChanged(handler_id) => {
+                self.remove_on_value_changed(handler_id);
+                Some(Box::new(()))
+            }
+        });
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button != MouseButton::Left {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        if !self.skin.is_hot_area(x, y) {
+            self.state.focused = false;
+            return false;
+        }
+        self.state.focused = true;
+        self.state.dragging = true;
+        if self.skin.is_thumb_area(x, y) {
+            self.drag_offset = self.state.fraction() - self.skin.fraction_at(x, y);
+        } else {
+            self.drag_offset = 0.;
+            let value = self.value_at_fraction(self.skin.fraction_at(x, y));
+            self.change_value(value);
+        }
+        true
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> bool {
+        if button == MouseButton::Left && self.state.dragging {
+            self.state.dragging = false;
+            return true;
+        }
+        false
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> bool {
+        if self.state.dragging {
+            let value = self.value_at_fraction(self.skin.fraction_at(x, y) + self.drag_offset);
+            self.change_value(value);
+            return true;
+        }
+        false
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        _keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        if !self.state.focused {
+            return false;
+        }
+        let step = self.state.key_step();
+        let value = self.state.value;
+        let value = match keycode {
+            KeyCode::Left | KeyCode::Down => value - step,
+            KeyCode::Right | KeyCode::Up => value + step,
+            KeyCode::PageDown => value - step * PAGE_STEPS,
+            KeyCode::PageUp => value + step * PAGE_STEPS,
+            KeyCode::Home => self.state.min,
+            KeyCode::End => self.state.max,
+            _ => return false,
+        };
+        self.change_value(value);
+        true
+    }
+}
+
+pub struct SliderBuilder<S: SliderSkin> {
+    slider: Slider<S>,
+}
+
+impl<S: SliderSkin> Default for SliderBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: SliderSkin> SliderBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            slider: Slider::new(),
+        }
+    }
+    pub fn set_orientation(mut self, orientation: RibbonOrientation) -> Self {
+        self.slider.set_orientation(orientation);
+        self
+    }
+    pub fn set_range(mut self, min: f32, max: f32) -> Self {
+        self.slider.set_range(min, max);
+        self
+    }
+    pub fn set_step(mut self, step: f32) -> Self {
+        self.slider.set_step(step);
+        self
+    }
+    pub fn set_value(mut self, value: f32) -> Self {
+        self.slider.set_value(value);
+        self
+    }
+    pub fn on_value_changed<F: Fn(&mut dyn Widget, f32) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.slider.on_value_changed(f);
+        self
+    }
+    pub fn build(self) -> Slider<S> {
+        self.slider
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::graphics::Rect;
+use ggez::{timer, Context, GameResult};
+use std::fmt::Debug;
+
+/// Seconds for one revolution of the spinner
+const SPINNER_PERIOD: f32 = 1.;
+
+#[derive(Clone, Debug)]
+pub struct SpinnerState {
+    pub active: bool,
+    /// Rotation phase from 0 to 1
+    pub phase: f32,
+    pub rect: Rect,
+}
+
+impl Default for SpinnerState {
+    fn default() -> Self {
+        Self {
+            active: true,
+            phase: 0.,
+            rect: Rect::default(),
+        }
+    }
+}
+
+pub trait SpinnerSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &SpinnerState);
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpinnerId(SrvId);
+
+impl From<SpinnerId> for SrvId {
+    fn from(v: SpinnerId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum SpinnerOp {
+    IsActive,
+    SetActive(bool),
+}
+
+impl SpinnerId {
+    pub async fn is_active(self) -> bool {
+        send_request(self.0, SpinnerOp::IsActive).await.unwrap()
+    }
+    pub async fn set_active(self, active: bool) {
+        send_request(self.0, SpinnerOp::SetActive(active))
+            .await
+            .unwrap()
+    }
+}
+
+pub struct Spinner<S: SpinnerSkin> {
+    state: SpinnerState,
+    skin: S,
+    reg: ServiceRegistration,
+}
+
+impl<S: SpinnerSkin> Spinner<S> {
+    pub fn new() -> Self {
+        Self {
+            state: SpinnerState::default(),
+            skin: S::default(),
+            reg: register_service(),
+        }
+    }
+    pub fn id(&self) -> SpinnerId {
+        SpinnerId(self.reg.id())
+    }
+    pub fn set_active(&mut self, active: bool) {
+        self.state.active = active
+    }
+    pub fn is_active(&self) -> bool {
+        self.state.active
+    }
+}
+
+impl<S: SpinnerSkin> Default for Spinner<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: SpinnerSkin + 'static> Widget for Spinner<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![("active", self.state.active.to_string())]
+    }
+}
+
+impl<S: SpinnerSkin + 'static> EventHandlerProxy for Spinner<S> {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            SpinnerOp::IsActive => Some(Box::new(self.is_active())),
+            SpinnerOp::SetActive(active) => {
+                self.set_active(active);
+                Some(Box::new(()))
+            }
+        });
+        if self.state.active {
+            let dt = timer::delta(ctx).as_secs_f32();
+            self.state.phase = (self.state.phase + dt / SPINNER_PERIOD).fract();
+        }
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+}
+
+pub struct SpinnerBuilder<S: SpinnerSkin> {
+    spinner: Spinner<S>,
+}
+
+impl<S: SpinnerSkin> Default for SpinnerBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: SpinnerSkin> SpinnerBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            spinner: Spinner::new(),
+        }
+    }
+    pub fn set_active(mut self, active: bool) -> Self {
+        self.spinner.set_active(active);
+        self
+    }
+    pub fn build(self) -> Spinner<S> {
+        self.spinner
+    }
+}
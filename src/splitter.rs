@@ -0,0 +1,541 @@
+use crate::overlay::OverlayId;
+use crate::ribbon::RibbonOrientation;
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::{timer, Context, GameResult};
+use std::fmt::Debug;
+use std::time::Duration;
+
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+#[derive(Clone, Default, Debug)]
+pub struct SplitterState {
+    /// Horizontal splitter places panes from left to right
+    pub orientation: RibbonOrientation,
+    /// Centers of the dividers as fractions of the splitter length, ascending
+    pub positions: Vec<f32>,
+    pub hovered: Option<usize>,
+    pub dragging: Option<usize>,
+    pub rect: Rect,
+}
+
+impl SplitterState {
+    fn is_vertical(&self) -> bool {
+        matches!(self.orientation, RibbonOrientation::Vertical)
+    }
+    /// Size of the splitter along the orientation
+    pub fn length(&self) -> f32 {
+        if self.is_vertical() {
+            self.rect.h
+        } else {
+            self.rect.w
+        }
+    }
+    /// Part of the rect from `start` to `end` along the orientation
+    fn span(&self, start: f32, end: f32) -> Rect {
+        let rect = self.rect;
+        if self.is_vertical() {
+            Rect::new(rect.x, rect.y + start, rect.w, end - start)
+        } else {
+            Rect::new(rect.x + start, rect.y, end - start, rect.h)
+        }
+    }
+    pub fn divider_rect(&self, index: usize, divider_size: f32) -> Rect {
+        let center = self.positions[index] * self.length();
+        self.span(center - divider_size / 2., center + divider_size / 2.)
+    }
+    pub fn pane_rect(&self, index: usize, divider_size: f32) -> Rect {
+        let length = self.length();
+        let start = if index == 0 {
+            0.
+        } else {
+            self.positions[index - 1] * length + divider_size / 2.
+        };
+        let end = if index == self.positions.len() {
+            length
+        } else {
+            self.positions[index] * length - divider_size / 2.
+        };
+        self.span(start, end.max(start))
+    }
+}
+
+pub trait SplitterSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &SplitterState);
+    /// Thickness of the dividers between panes
+    fn divider_size(&self) -> f32;
+    fn divider_at(&self, x: f32, y: f32) -> Option<usize>;
+}
+
+struct Pane {
+    widget: Box<dyn Widget>,
+    min_size: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SplitterId(SrvId);
+
+impl From<SplitterId> for SrvId {
+    fn from(v: SplitterId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum SplitterOp {
+    GetPositions,
+    SetPositions(Vec<f32>),
+    AddPane(Box<dyn Widget>, f32),
+}
+
+impl SplitterId {
+    pub async fn get_positions(self) -> Vec<f32> {
+        send_request(self.0, SplitterOp::GetPositions)
+            .await
+            .unwrap()
+    }
+    /// Divider positions as fractions of the splitter length, adjusted to
+    /// minimal pane sizes
+    pub async fn set_positions(self, positions: Vec<f32>) {
+        send_request(self.0, SplitterOp::SetPositions(positions))
+            .await
+            .unwrap()
+    }
+    pub async fn add_pane(self, widget: impl Widget + 'static, min_size: f32) {
+        send_request(self.0, SplitterOp::AddPane(Box::new(widget), min_size))
+            .await
+            .unwrap()
+    }
+}
+
+/// Panes separated by draggable dividers. Double click on a divider collapses
+/// the smaller neighbour pane and the next one restores it.
+pub struct Splitter<S: SplitterSkin> {
+    state: SplitterState,
+    skin: S,
+    reg: ServiceRegistration,
+    panes: Vec<Pane>,
+    /// Positions to restore collapsed panes, by divider
+    collapsed: Vec<Option<f32>>,
+    /// Divider and time of the last press, for double click detection
+    last_press: Option<(usize, Duration)>,
+    overlay: Option<OverlayId>,
+}
+
+impl<S: SplitterSkin> Splitter<S> {
+    pub fn new() -> Self {
+        Self {
+            state: SplitterState::default(),
+            skin: S::default(),
+            reg: register_service(),
+            panes: Vec::new(),
+            collapsed: Vec::new(),
+            last_press: None,
+            overlay: None,
+        }
+    }
+    pub fn id(&self) -> SplitterId {
+        SplitterId(self.reg.id())
+    }
+    pub fn set_orientation(&mut self, orientation: RibbonOrientation) {
+        self.state.orientation = orientation;
+        self.update_widgets_rects();
+    }
+    pub fn get_orientation(&self) -> RibbonOrientation {
+        self.state.orientation
+    }
+    /// Resets dividers to split the space equally
+    pub fn add_pane_box(&mut self, mut widget: Box<dyn Widget>, min_size: f32) {
+        if let Some(overlay) = self.overlay {
+            widget.attach_overlay(overlay)
+        }
+        self.panes.push(Pane { widget, min_size });
+        let count = self.panes.len();
+        self.state.positions = (1..count).map(|n| n as f32 / count as f32).collect();
+        self.collapsed = vec![None; count - 1];
+        self.update_widgets_rects();
+    }
+    pub fn add_pane(&mut self, widget: impl Widget + 'static, min_size: f32) {
+        self.add_pane_box(Box::new(widget), min_size)
+    }
+    pub fn get_positions(&self) -> &[f32] {
+        &self.state.positions
+    }
+    pub fn set_positions(&mut self, positions: Vec<f32>) {
+        for (index, position) in positions.into_iter().enumerate() {
+            if index < self.state.positions.len() {
+                self.state.positions[index] = position;
+                self.collapsed[index] = None;
+            }
+        }
+        self.clamp_positions();
+        self.update_widgets_rects();
+    }
+    /// Clamps all dividers except the ones of collapsed panes
+    fn clamp_positions(&mut self) {
+        for index in 0..self.state.positions.len() {
+            if self.collapsed[index].is_none() {
+                let position = self.clamp_position(index, self.state.positions[index]);
+                self.state.positions[index] = position;
+            }
+        }
+    }
+    /// Keeps divider between its neighbours leaving minimal sizes to the panes
+    fn clamp_position(&self, index: usize, position: f32) -> f32 {
+        let length = self.state.length();
+        if length <= 0. {
+            return position.clamp(0., 1.);
+        }
+        let divider = self.skin.divider_size();
+        let before = if index == 0 {
+            0.
+        } else {
+            self.state.positions[index - 1] * length + divider / 2.
+        };
+        let after = if index + 1 == self.state.positions.len() {
+            length
+        } else {
+            self.state.positions[index + 1] * length - divider / 2.
+        };
+        let lower = before + self.panes[index].min_size + divider / 2.;
+        let upper = after - self.panes[index + 1].min_size - divider / 2.;
+        let center = position * length;
+        let center = if lower > upper {
+            (lower + upper) / 2.
+        } else {
+            center.max(lower).min(upper)
+        };
+        center / length
+    }
+    fn toggle_collapsed(&mut self, index: usize) {
+        if let Some(position) = self.collapsed[index].take() {
+            self.state.positions[index] = self.clamp_position(index, position);
+        } else {
+            let divider = self.skin.divider_size();
+            let before = self.state.pane_rect(index, divider);
+            let after = self.state.pane_rect(index + 1, divider);
+            let (size_before, size_after) = if self.state.is_vertical() {
+                (before.h, after.h)
+            } else {
+                (before.w, after.w)
+            };
+            let length = self.state.length();
+            self.collapsed[index] = Some(self.state.positions[index]);
+            // collapsed pane takes no space, its minimal size is ignored
+            let shift = if size_before <= size_after {
+                -size_before
+            } else {
+                size_after
+            };
+            self.state.positions[index] += shift / length;
+        }
+        self.update_widgets_rects();
+    }
+    fn update_widgets_rects(&mut self) {
+        self.skin.set_state(&self.state);
+        let divider = self.skin.divider_size();
+        for (index, pane) in self.panes.iter_mut().enumerate() {
+            pane.widget.set_rect(self.state.pane_rect(index, divider));
+        }
+    }
+}
+
+impl<S: SplitterSkin> Default for Splitter<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: SplitterSkin + 'static> Widget for Splitter<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+        self.skin.set_state(&self.state);
+        self.clamp_positions();
+        self.update_widgets_rects();
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.panes
+            .iter()
+            .map(|p| p.widget.as_ref() as &dyn Widget)
+            .collect()
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.panes
+            .iter_mut()
+            .map(|p| p.widget.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("orientation", format!("{:?}", self.get_orientation())),
+            ("positions", format!("{:?}", self.state.positions)),
+        ]
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.overlay = Some(overlay);
+        for p in &mut self.panes {
+            p.widget.attach_overlay(overlay)
+        }
+    }
+}
+
+impl<S: SplitterSkin + 'static> EventHandlerProxy for Splitter<S> {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            SplitterOp::GetPositions => Some(Box::new(self.get_positions().to_vec())),
+            SplitterOp::SetPositions(positions) => {
+                self.set_positions(positions);
+                Some(Box::new(()))
+            }
+            SplitterOp::AddPane(widget, min_size) => {
+                self.add_pane_box(widget, min_size);
+                Some(Box::new(()))
+            }
+        });
+        for p in &mut self.panes {
+            p.widget.update(ctx)?
+        }
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        for p in &mut self.panes {
+            p.widget.draw(ctx)?
+        }
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button == MouseButton::Left {
+            self.skin.set_state(&self.state);
+            if let Some(index) = self.skin.divider_at(x, y) {
+                let now = timer::time_since_start(ctx);
+                match self.last_press {
+                    Some((last, time)) if last == index && now - time < DOUBLE_CLICK_TIME => {
+                        self.last_press = None;
+                        self.toggle_collapsed(index);
+                    }
+                    _ => {
+                        self.last_press = Some((index, now));
+                        self.state.dragging = Some(index);
+                    }
+                }
+                return true;
+            }
+        }
+        let mut consumed = false;
+        for p in &mut self.panes {
+            consumed |= p.widget.mouse_button_down_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button == MouseButton::Left && self.state.dragging.take().is_some() {
+            return true;
+        }
+        let mut consumed = false;
+        for p in &mut self.panes {
+            consumed |= p.widget.mouse_button_up_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        if let Some(index) = self.state.dragging {
+            let offset = if self.state.is_vertical() {
+                y - self.state.rect.y
+            } else {
+                x - self.state.rect.x
+            };
+            let length = self.state.length();
+            if length > 0. {
+                self.collapsed[index] = None;
+                self.state.positions[index] = self.clamp_position(index, offset / length);
+                self.update_widgets_rects();
+            }
+            return true;
+        }
+        self.skin.set_state(&self.state);
+        self.state.hovered = self.skin.divider_at(x, y);
+        let mut consumed = false;
+        for p in &mut self.panes {
+            consumed |= p.widget.mouse_motion_event(ctx, x, y, dx, dy)
+        }
+        consumed
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        let mut consumed = false;
+        for p in &mut self.panes {
+            consumed |= p.widget.mouse_wheel_event(ctx, x, y)
+        }
+        consumed
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        let mut consumed = false;
+        for p in &mut self.panes {
+            consumed |= p.widget.key_down_event(ctx, keycode, keymods, repeat)
+        }
+        consumed
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        let mut consumed = false;
+        for p in &mut self.panes {
+            consumed |= p.widget.key_up_event(ctx, keycode, keymods)
+        }
+        consumed
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        let mut consumed = false;
+        for p in &mut self.panes {
+            consumed |= p.widget.text_input_event(ctx, character)
+        }
+        consumed
+    }
+}
+
+pub struct SplitterBuilder<S: SplitterSkin> {
+    splitter: Splitter<S>,
+}
+
+impl<S: SplitterSkin + 'static> Default for SplitterBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: SplitterSkin + 'static> SplitterBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            splitter: Splitter::new(),
+        }
+    }
+    pub fn set_orientation(mut self, orientation: RibbonOrientation) -> Self {
+        self.splitter.set_orientation(orientation);
+        self
+    }
+    /// Pane is never made smaller than `min_size` by dragging
+    pub fn add_pane(mut self, widget: impl Widget + 'static, min_size: f32) -> Self {
+        self.splitter.add_pane(widget, min_size);
+        self
+    }
+    /// Call after all panes are added
+    pub fn set_positions(mut self, positions: Vec<f32>) -> Self {
+        self.splitter.set_positions(positions);
+        self
+    }
+    pub fn build(self) -> Splitter<S> {
+        self.splitter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ribbon::Ribbon;
+
+    #[derive(Default, Debug)]
+    struct TestSkin;
+
+    impl EventHandlerProxy for TestSkin {}
+
+    impl SplitterSkin for TestSkin {
+        fn set_state(&mut self, _state: &SplitterState) {}
+        fn divider_size(&self) -> f32 {
+            10.
+        }
+        fn divider_at(&self, _x: f32, _y: f32) -> Option<usize> {
+            None
+        }
+    }
+
+    fn new_splitter(min_sizes: [f32; 2], rect: Option<Rect>) -> Splitter<TestSkin> {
+        let mut splitter = Splitter::new();
+        for min_size in min_sizes {
+            splitter.add_pane(Ribbon::new(), min_size);
+        }
+        if let Some(rect) = rect {
+            splitter.set_rect(rect);
+        }
+        splitter
+    }
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn position_leaves_minimal_pane_sizes() {
+        let splitter = new_splitter([20., 30.], Some(Rect::new(0., 0., 100., 10.)));
+        assert_near(splitter.clamp_position(0, 0.), 0.25);
+        assert_near(splitter.clamp_position(0, 1.), 0.65);
+        assert_near(splitter.clamp_position(0, 0.4), 0.4);
+        // panes don't fit, divider stays in the middle of the allowed range
+        let crowded = new_splitter([60., 60.], Some(Rect::new(0., 0., 100., 10.)));
+        assert_near(crowded.clamp_position(0, 0.1), 0.5);
+    }
+
+    #[test]
+    fn positions_are_clamped_again_when_rect_is_set() {
+        let mut splitter = new_splitter([20., 30.], None);
+        splitter.set_positions(vec![0.]);
+        assert_eq!(splitter.get_positions(), &[0.]);
+        splitter.set_rect(Rect::new(0., 0., 100., 10.));
+        assert_near(splitter.get_positions()[0], 0.25);
+        splitter.set_rect(Rect::new(0., 0., 50., 10.));
+        assert_near(splitter.get_positions()[0], 0.4);
+    }
+
+    #[test]
+    fn smaller_pane_is_collapsed_and_restored() {
+        let mut splitter = new_splitter([20., 0.], Some(Rect::new(0., 0., 100., 10.)));
+        splitter.set_positions(vec![0.3]);
+        splitter.toggle_collapsed(0);
+        // collapsed pane ignores its minimal size
+        assert_near(splitter.get_positions()[0], 0.05);
+        assert_eq!(splitter.state.pane_rect(0, 10.).w, 0.);
+        // collapsed divider isn't moved by resizing
+        splitter.set_rect(Rect::new(0., 0., 200., 10.));
+        assert_near(splitter.get_positions()[0], 0.05);
+        splitter.toggle_collapsed(0);
+        assert_near(splitter.get_positions()[0], 0.3);
+
+        splitter.set_positions(vec![0.8]);
+        splitter.toggle_collapsed(0);
+        assert_near(splitter.get_positions()[0], 0.975);
+        assert_eq!(splitter.state.pane_rect(1, 10.).w, 0.);
+    }
+}
@@ -0,0 +1,383 @@
+use crate::overlay::OverlayId;
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::{Context, GameResult};
+
+/// What inactive pages of the stack do in `update`
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum InactivePagesPolicy {
+    /// Not updated at all, requests to them wait until the page is shown
+    #[default]
+    Suspend,
+    /// Updated as usual, so they keep serving requests while hidden
+    Update,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StackId(SrvId);
+
+impl From<StackId> for SrvId {
+    fn from(v: StackId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum StackOp {
+    AddPage(Box<dyn Widget>),
+    RemovePage(SrvId),
+    Show(SrvId),
+    Next,
+    Prev,
+    GetActive,
+    SetInactivePagesPolicy(InactivePagesPolicy),
+}
+
+impl StackId {
+    pub async fn add_page(self, page: impl Widget + 'static) {
+        send_request(self.0, StackOp::AddPage(Box::new(page)))
+            .await
+            .unwrap()
+    }
+    /// Returns false if there is no such page
+    pub async fn remove_page(self, page: SrvId) -> bool {
+        send_request(self.0, StackOp::RemovePage(page))
+            .await
+            .unwrap()
+    }
+    /// Returns false if there is no such page
+    pub async fn show(self, page: SrvId) -> bool {
+        send_request(self.0, StackOp::Show(page)).await.unwrap()
+    }
+    /// Returns false if the last page is already shown
+    pub async fn next(self) -> bool {
+        send_request(self.0, StackOp::Next).await.unwrap()
+    }
+    /// Returns false if the first page is already shown
+    pub async fn prev(self) -> bool {
+        send_request(self.0, StackOp::Prev).await.unwrap()
+    }
+    pub async fn get_active(self) -> Option<SrvId> {
+        send_request(self.0, StackOp::GetActive).await.unwrap()
+    }
+    pub async fn set_inactive_pages_policy(self, policy: InactivePagesPolicy) {
+        send_request(self.0, StackOp::SetInactivePagesPolicy(policy))
+            .await
+            .unwrap()
+    }
+}
+
+/// Holds many pages but lays out, draws and passes events only to the active one
+pub struct Stack {
+    pages: Vec<Box<dyn Widget>>,
+    active: Option<usize>,
+    policy: InactivePagesPolicy,
+    rect: Rect,
+    overlay: Option<OverlayId>,
+    reg: ServiceRegistration,
+}
+
+impl Stack {
+    pub fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            active: None,
+            policy: InactivePagesPolicy::default(),
+            rect: Rect::default(),
+            overlay: None,
+            reg: register_service(),
+        }
+    }
+    pub fn id(&self) -> StackId {
+        StackId(self.reg.id())
+    }
+    pub fn set_inactive_pages_policy(&mut self, policy: InactivePagesPolicy) {
+        self.policy = policy
+    }
+    pub fn get_inactive_pages_policy(&self) -> InactivePagesPolicy {
+        self.policy
+    }
+    pub fn add_page_box(&mut self, mut page: Box<dyn Widget>) {
+        if let Some(overlay) = self.overlay {
+            page.attach_overlay(overlay)
+        }
+        self.pages.push(page);
+        if self.active.is_none() {
+            self.show_index(self.pages.len() - 1);
+        }
+    }
+    pub fn add_page(&mut self, page: impl Widget + 'static) {
+        self.add_page_box(Box::new(page))
+    }
+    pub fn remove_page(&mut self, page: SrvId) -> Option<Box<dyn Widget>> {
+        let index = self.index_of(page)?;
+        let page = self.pages.remove(index);
+        match self.active {
+            _ if self.pages.is_empty() => self.active = None,
+            Some(active) if active > index => self.active = Some(active - 1),
+            Some(active) if active == index => self.show_index(index.min(self.pages.len() - 1)),
+            _ => {}
+        }
+        Some(page)
+    }
+    pub fn show(&mut self, page: SrvId) -> bool {
+        match self.index_of(page) {
+            Some(index) => {
+                self.show_index(index);
+                true
+            }
+            None => false,
+        }
+    }
+    /// Returns false if the last page is already shown
+    pub fn show_next(&mut self) -> bool {
+        match self.active {
+            Some(active) if active + 1 < self.pages.len() => {
+                self.show_index(active + 1);
+                true
+            }
+            _ => false,
+        }
+    }
+    /// Returns false if the first page is already shown
+    pub fn show_prev(&mut self) -> bool {
+        match self.active {
+            Some(active) if active > 0 => {
+                self.show_index(active - 1);
+                true
+            }
+            _ => false,
+        }
+    }
+    pub fn get_active(&self) -> Option<SrvId> {
+        self.active.map(|index| self.pages[index].srv_id())
+    }
+    /// All pages including the hidden ones
+    pub fn all_pages(&self) -> Vec<&dyn Widget> {
+        self.pages.iter().map(|w| w.as_ref()).collect()
+    }
+    fn index_of(&self, page: SrvId) -> Option<usize> {
+        self.pages.iter().position(|w| w.srv_id() == page)
+    }
+    fn show_index(&mut self, index: usize) {
+        self.active = Some(index);
+        // inactive pages are not laid out, so actual rect is set on showing
+        let rect = self.rect;
+        self.pages[index].set_rect(rect);
+    }
+    fn active_page(&mut self) -> Option<&mut Box<dyn Widget>> {
+        let active = self.active?;
+        Some(&mut self.pages[active])
+    }
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Stack {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        if let Some(page) = self.active_page() {
+            page.set_rect(rect)
+        }
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.rect)
+    }
+    /// Only the active page, hidden ones are reachable through `all_pages`
+    fn children(&self) -> Vec<&dyn Widget> {
+        match self.active {
+            Some(active) => vec![self.pages[active].as_ref()],
+            None => Vec::new(),
+        }
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        match self.active_page() {
+            Some(page) => vec![page.as_mut()],
+            None => Vec::new(),
+        }
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("pages", self.pages.len().to_string()),
+            ("active", format!("{:?}", self.active)),
+            ("inactive_pages", format!("{:?}", self.policy)),
+        ]
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.overlay = Some(overlay);
+        for page in &mut self.pages {
+            page.attach_overlay(overlay)
+        }
+    }
+}
+
+impl EventHandlerProxy for Stack {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            StackOp::AddPage(page) => {
+                self.add_page_box(page);
+                Some(Box::new(()))
+            }
+            StackOp::RemovePage(page) => Some(Box::new(self.remove_page(page).is_some())),
+            StackOp::Show(page) => Some(Box::new(self.show(page))),
+            StackOp::Next => Some(Box::new(self.show_next())),
+            StackOp::Prev => Some(Box::new(self.show_prev())),
+            StackOp::GetActive => Some(Box::new(self.get_active())),
+            StackOp::SetInactivePagesPolicy(policy) => {
+                self.set_inactive_pages_policy(policy);
+                Some(Box::new(()))
+            }
+        });
+        match self.policy {
+            InactivePagesPolicy::Suspend => {
+                if let Some(page) = self.active_page() {
+                    page.update(ctx)?
+                }
+            }
+            InactivePagesPolicy::Update => {
+                for page in &mut self.pages {
+                    page.update(ctx)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        match self.active_page() {
+            Some(page) => page.draw(ctx),
+            None => Ok(()),
+        }
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        match self.active_page() {
+            Some(page) => page.mouse_button_down_event(ctx, button, x, y),
+            None => false,
+        }
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        match self.active_page() {
+            Some(page) => page.mouse_button_up_event(ctx, button, x, y),
+            None => false,
+        }
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        match self.active_page() {
+            Some(page) => page.mouse_motion_event(ctx, x, y, dx, dy),
+            None => false,
+        }
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        match self.active_page() {
+            Some(page) => page.mouse_wheel_event(ctx, x, y),
+            None => false,
+        }
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        match self.active_page() {
+            Some(page) => page.key_down_event(ctx, keycode, keymods, repeat),
+            None => false,
+        }
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        match self.active_page() {
+            Some(page) => page.key_up_event(ctx, keycode, keymods),
+            None => false,
+        }
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        match self.active_page() {
+            Some(page) => page.text_input_event(ctx, character),
+            None => false,
+        }
+    }
+}
+
+pub struct StackBuilder {
+    stack: Stack,
+}
+
+impl Default for StackBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StackBuilder {
+    pub fn new() -> Self {
+        Self {
+            stack: Stack::new(),
+        }
+    }
+    pub fn set_inactive_pages_policy(mut self, policy: InactivePagesPolicy) -> Self {
+        self.stack.set_inactive_pages_policy(policy);
+        self
+    }
+    pub fn add_page(mut self, page: impl Widget + 'static) -> Self {
+        self.stack.add_page(page);
+        self
+    }
+    pub fn build(self) -> Stack {
+        self.stack
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_active_page_is_a_child() {
+        let pages = [Stack::new(), Stack::new(), Stack::new()];
+        let ids: Vec<SrvId> = pages.iter().map(|page| page.srv_id()).collect();
+        let mut stack = Stack::new();
+        for page in pages {
+            stack.add_page(page);
+        }
+        let children =
+            |stack: &Stack| -> Vec<SrvId> { stack.children().iter().map(|w| w.srv_id()).collect() };
+        assert_eq!(children(&stack), vec![ids[0]]);
+        assert!(stack.show_next());
+        assert_eq!(children(&stack), vec![ids[1]]);
+        assert_eq!(stack.children_mut().len(), 1);
+        assert_eq!(stack.all_pages().len(), 3);
+        let stack: &dyn Widget = &stack;
+        assert!(stack.find_widget(ids[0]).is_none());
+        assert!(stack.find_widget(ids[1]).is_some());
+    }
+}
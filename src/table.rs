@@ -0,0 +1,677 @@
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::input::mouse;
+use ggez::{Context, GameResult};
+use indexmap::map::IndexMap;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+
+const WHEEL_STEP: usize = 3;
+const MIN_COLUMN_WIDTH: f32 = 20.;
+
+/// Data shown by the `Table`. Only cells of the visible rows are requested.
+pub trait TableModel: Send {
+    fn row_count(&self) -> usize;
+    fn cell(&self, row: usize, column: usize) -> String;
+    /// Order of the rows when the table is sorted by the column. By default
+    /// cells are compared as strings, so "10" goes before "9"; models with
+    /// numeric columns should compare their values here.
+    fn compare(&self, a: usize, b: usize, column: usize) -> Ordering {
+        self.cell(a, column).cmp(&self.cell(b, column))
+    }
+}
+
+impl TableModel for Vec<Vec<String>> {
+    fn row_count(&self) -> usize {
+        self.len()
+    }
+    fn cell(&self, row: usize, column: usize) -> String {
+        self.get(row)
+            .and_then(|r| r.get(column))
+            .cloned()
+            .unwrap_or_default()
+    }
+    fn compare(&self, a: usize, b: usize, column: usize) -> Ordering {
+        let cell = |row: usize| self[row].get(column);
+        cell(a).cmp(&cell(b))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct TableColumn {
+    pub title: String,
+    pub width: f32,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct TableRow {
+    pub cells: Vec<String>,
+    pub selected: bool,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct TableState {
+    pub columns: Vec<TableColumn>,
+    /// Column the rows are sorted by
+    pub sort: Option<(usize, SortOrder)>,
+    pub row_count: usize,
+    /// Index of the first visible row
+    pub scroll: usize,
+    /// Cells of the visible rows starting from `scroll`
+    pub rows: Vec<TableRow>,
+    /// Column which width is being dragged
+    pub resizing: Option<usize>,
+    pub focused: bool,
+    pub rect: Rect,
+}
+
+pub trait TableSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &TableState);
+    fn is_hot_area(&self, x: f32, y: f32) -> bool;
+    fn header_at(&self, x: f32, y: f32) -> Option<usize>;
+    /// Column which right edge in the header is under the point
+    fn divider_at(&self, x: f32, y: f32) -> Option<usize>;
+    /// Row in sorted order under the point
+    fn row_at(&self, x: f32, y: f32) -> Option<usize>;
+    /// Number of fully visible rows
+    fn visible_count(&self) -> usize;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TableId(SrvId);
+
+impl From<TableId> for SrvId {
+    fn from(v: TableId) -> SrvId {
+        v.0
+    }
+}
+
+type SelectionChangedHandler = Box<dyn Fn(&mut dyn Widget, usize) + Send + Sync>;
+
+enum TableOp {
+    SetModel(Box<dyn TableModel>),
+    Refresh,
+    GetSelection,
+    SetSelection(Option<usize>),
+    GetSort,
+    SortBy(Option<(usize, SortOrder)>),
+    GetColumnWidths,
+    SetColumnWidth(usize, f32),
+    OnSelectionChanged(SelectionChangedHandler),
+    RemoveOnSelectionChanged(usize),
+}
+
+impl Debug for TableOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableOp::SetModel(_) => write!(f, "SetModel"),
+            TableOp::Refresh => write!(f, "Refresh"),
+            TableOp::GetSelection => write!(f, "GetSelection"),
+            TableOp::SetSelection(row) => write!(f, "SetSelection({:?})", row),
+            TableOp::GetSort => write!(f, "GetSort"),
+            TableOp::SortBy(sort) => write!(f, "SortBy({:?})", sort),
+            TableOp::GetColumnWidths => write!(f, "GetColumnWidths"),
+            TableOp::SetColumnWidth(column, width) => {
+                write!(f, "SetColumnWidth({:?}, {:?})", column, width)
+            }
+            TableOp::OnSelectionChanged(_) => write!(f, "OnSelectionChanged"),
+            TableOp::RemoveOnSelectionChanged(handler_id) => {
+                write!(f, "RemoveOnSelectionChanged({:?})", handler_id)
+            }
+        }
+    }
+}
+
+impl TableId {
+    pub async fn set_model<M: TableModel + 'static>(self, model: M) {
+        send_request(self.0, TableOp::SetModel(Box::new(model)))
+            .await
+            .unwrap()
+    }
+    /// Sorts and shows the rows again after the model data was changed
+    pub async fn refresh(self) {
+        send_request(self.0, TableOp::Refresh).await.unwrap()
+    }
+    /// Selected row index in the model
+    pub async fn get_selection(self) -> Option<usize> {
+        send_request(self.0, TableOp::GetSelection).await.unwrap()
+    }
+    pub async fn set_selection(self, row: Option<usize>) {
+        send_request(self.0, TableOp::SetSelection(row))
+            .await
+            .unwrap()
+    }
+    pub async fn get_sort(self) -> Option<(usize, SortOrder)> {
+        send_request(self.0, TableOp::GetSort).await.unwrap()
+    }
+    pub async fn sort_by(self, sort: Option<(usize, SortOrder)>) {
+        send_request(self.0, TableOp::SortBy(sort)).await.unwrap()
+    }
+    pub async fn get_column_widths(self) -> Vec<f32> {
+        send_request(self.0, TableOp::GetColumnWidths)
+            .await
+            .unwrap()
+    }
+    pub async fn set_column_width(self, column: usize, width: f32) {
+        send_request(self.0, TableOp::SetColumnWidth(column, width))
+            .await
+            .unwrap()
+    }
+    pub async fn on_selection_changed<F: Fn(&mut dyn Widget, usize) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, TableOp::OnSelectionChanged(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_selection_changed(self, handler_id: usize) {
+        send_request(self.0, TableOp::RemoveOnSelectionChanged(handler_id))
+            .await
+            .unwrap()
+    }
+}
+
+/// Rows of the model under column headers. Clicking the header sorts rows by
+/// the column, dragging the header's right edge resizes the column.
+pub struct Table<S: TableSkin> {
+    state: TableState,
+    skin: S,
+    reg: ServiceRegistration,
+    model: Box<dyn TableModel>,
+    /// Model row indices in the displayed order
+    order: Vec<usize>,
+    /// Selected model row
+    selection: Option<usize>,
+    /// Mouse x and column width when resizing started
+    resize_start: (f32, f32),
+    on_selection_changed_handlers: IndexMap<usize, SelectionChangedHandler>,
+}
+
+impl<S: TableSkin> Table<S> {
+    pub fn new() -> Self {
+        Self {
+            state: TableState::default(),
+            skin: S::default(),
+            reg: register_service(),
+            model: Box::new(Vec::<Vec<String>>::new()),
+            order: Vec::new(),
+            selection: None,
+            resize_start: (0., 0.),
+            on_selection_changed_handlers: IndexMap::new(),
+        }
+    }
+    pub fn id(&self) -> TableId {
+        TableId(self.reg.id())
+    }
+    pub fn add_column<T: Into<String>>(&mut self, title: T, width: f32) {
+        self.state.columns.push(TableColumn {
+            title: title.into(),
+            width: width.max(MIN_COLUMN_WIDTH),
+        })
+    }
+    pub fn get_columns(&self) -> &[TableColumn] {
+        &self.state.columns
+    }
+    pub fn set_column_width(&mut self, column: usize, width: f32) {
+        if let Some(c) = self.state.columns.get_mut(column) {
+            c.width = width.max(MIN_COLUMN_WIDTH)
+        }
+    }
+    pub fn set_model_box(&mut self, model: Box<dyn TableModel>) {
+        self.model = model;
+        self.refresh();
+    }
+    pub fn set_model<M: TableModel + 'static>(&mut self, model: M) {
+        self.set_model_box(Box::new(model))
+    }
+    pub fn get_model(&self) -> &dyn TableModel {
+        self.model.as_ref()
+    }
+    /// Keeps selection if the selected row still exists
+    pub fn refresh(&mut self) {
+        let count = self.model.row_count();
+        self.state.row_count = count;
+        self.selection = self.selection.filter(|&row| row < count);
+        self.sort();
+    }
+    pub fn get_sort(&self) -> Option<(usize, SortOrder)> {
+        self.state.sort
+    }
+    pub fn sort_by(&mut self, sort: Option<(usize, SortOrder)>) {
+        self.state.sort = sort.filter(|(column, _)| *column < self.state.columns.len());
+        self.sort();
+    }
+    pub fn get_selection(&self) -> Option<usize> {
+        self.selection
+    }
+    pub fn set_selection(&mut self, row: Option<usize>) {
+        self.selection = row.filter(|&row| row < self.state.row_count);
+        self.ensure_selection_visible();
+    }
+    pub fn on_selection_changed_box(&mut self, handler: SelectionChangedHandler) -> usize {
+        add_to_indexmap(&mut self.on_selection_changed_handlers, handler)
+    }
+    pub fn on_selection_changed<F: Fn(&mut dyn Widget, usize) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_selection_changed_box(Box::new(f))
+    }
+    pub fn remove_on_selection_changed(&mut self, handler_id: usize) {
+        self.on_selection_changed_handlers.remove(&handler_id);
+    }
+    fn sort(&mut self) {
+        self.order = (0..self.state.row_count).collect();
+        if let Some((column, sort_order)) = self.state.sort {
+            let model = &self.model;
+            self.order.sort_by(|&a, &b| {
+                let ordering = model.compare(a, b, column);
+                match sort_order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+        self.ensure_selection_visible();
+    }
+    fn selected_position(&self) -> Option<usize> {
+        let selection = self.selection?;
+        self.order.iter().position(|&row| row == selection)
+    }
+    fn ensure_selection_visible(&mut self) {
+        self.skin.set_state(&self.state);
+        let visible = self.skin.visible_count().max(1);
+        if let Some(pos) = self.selected_position() {
+            if pos < self.state.scroll {
+                self.state.scroll = pos
+            } else if pos >= self.state.scroll + visible {
+                self.state.scroll = pos + 1 - visible
+            }
+        }
+        self.state.scroll = self
+            .state
+            .scroll
+            .min(self.state.row_count.saturating_sub(visible));
+    }
+    /// Takes cells of the visible rows from the model
+    fn update_rows(&mut self) {
+        self.skin.set_state(&self.state);
+        let end = (self.state.scroll + self.skin.visible_count()).min(self.order.len());
+        let columns = self.state.columns.len();
+        let model = &self.model;
+        let selection = self.selection;
+        self.state.rows = self.order[self.state.scroll.min(end)..end]
+            .iter()
+            .map(|&row| TableRow {
+                cells: (0..columns).map(|column| model.cell(row, column)).collect(),
+                selected: selection == Some(row),
+            })
+            .collect();
+    }
+}
+
+impl<S: TableSkin> Default for Table<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: TableSkin + 'static> Table<S> {
+    /// Selects the row at the position in sorted order and notifies handlers
+    fn select_position(&mut self, pos: usize) {
+        let row = match self.order.get(pos.min(self.order.len().saturating_sub(1))) {
+            Some(&row) => row,
+            None => return,
+        };
+        if self.selection == Some(row) {
+            return;
+        }
+        self.selection = Some(row);
+        self.ensure_selection_visible();
+        let handlers = std::mem::replace(&mut self.on_selection_changed_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, row);
+        }
+        self.on_selection_changed_handlers = handlers;
+    }
+    /// Clicking the sorted column again reverses the order
+    fn toggle_sort(&mut self, column: usize) {
+        let sort_order = match self.state.sort {
+            Some((c, SortOrder::Ascending)) if c == column => SortOrder::Descending,
+            _ => SortOrder::Ascending,
+        };
+        self.sort_by(Some((column, sort_order)))
+    }
+}
+
+impl<S: TableSkin + 'static> Widget for Table<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+        self.ensure_selection_visible();
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("columns", self.state.columns.len().to_string()),
+            ("rows", self.state.row_count.to_string()),
+            ("sort", format!("{:?}", self.state.sort)),
+            ("selection", format!("{:?}", self.selection)),
+        ]
+    }
+}
+
+impl<S: TableSkin + 'static> EventHandlerProxy for Table<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            TableOp::SetModel(model) => {
+                self.set_model_box(model);
+                Some(Box::new(()))
+            }
+            TableOp::Refresh => {
+                self.refresh();
+                Some(Box::new(()))
+            }
+            TableOp::GetSelection => Some(Box::new(self.get_selection())),
+            TableOp::SetSelection(row) => {
+                self.set_selection(row);
+                Some(Box::new(()))
+            }
+            TableOp::GetSort => Some(Box::new(self.get_sort())),
+            TableOp::SortBy(sort) => {
+                self.sort_by(sort);
+                Some(Box::new(()))
+            }
+            TableOp::GetColumnWidths => Some(Box::new(
+                self.state
+                    .columns
+                    .iter()
+                    .map(|c| c.width)
+                    .collect::<Vec<_>>(),
+            )),
+            TableOp::SetColumnWidth(column, width) => {
+                self.set_column_width(column, width);
+                Some(Box::new(()))
+            }
+            TableOp::OnSelectionChanged(handler) => {
+                Some(Box::new(self.on_selection_changed_box(handler)))
+            }
+            TableOp::RemoveOnSelectionChanged(handler_id) => {
+                self.remove_on_selection_changed(handler_id);
+                Some(Box::new(()))
+            }
+        });
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.update_rows();
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button != MouseButton::Left {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        if !self.skin.is_hot_area(x, y) {
+            self.state.focused = false;
+            return false;
+        }
+        self.state.focused = true;
+        if let Some(column) = self.skin.divider_at(x, y) {
+            self.state.resizing = Some(column);
+            self.resize_start = (x, self.state.columns[column].width);
+        } else if let Some(column) = self.skin.header_at(x, y) {
+            self.toggle_sort(column)
+        } else if let Some(pos) = self.skin.row_at(x, y) {
+            self.select_position(pos)
+        }
+        true
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> bool {
+        button == MouseButton::Left && self.state.resizing.take().is_some()
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        _ctx: &mut Context,
+        x: f32,
+        _y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> bool {
+        if let Some(column) = self.state.resizing {
+            let (start_x, start_width) = self.resize_start;
+            self.set_column_width(column, start_width + x - start_x);
+            return true;
+        }
+        false
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) -> bool {
+        let pos = mouse::position(ctx);
+        self.skin.set_state(&self.state);
+        if !self.skin.is_hot_area(pos.x, pos.y) {
+            return false;
+        }
+        let max_scroll = self
+            .state
+            .row_count
+            .saturating_sub(self.skin.visible_count());
+        if y > 0. {
+            self.state.scroll = self.state.scroll.saturating_sub(WHEEL_STEP);
+        } else if y < 0. {
+            self.state.scroll = (self.state.scroll + WHEEL_STEP).min(max_scroll);
+        }
+        true
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        _keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        if !self.state.focused || self.order.is_empty() {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        let page = self.skin.visible_count().max(1);
+        let pos = self.selected_position();
+        match keycode {
+            KeyCode::Up => self.select_position(pos.map_or(0, |n| n.saturating_sub(1))),
+            KeyCode::Down => self.select_position(pos.map_or(0, |n| n + 1)),
+            KeyCode::PageUp => self.select_position(pos.map_or(0, |n| n.saturating_sub(page))),
+            KeyCode::PageDown => self.select_position(pos.map_or(0, |n| n + page)),
+            KeyCode::Home => self.select_position(0),
+            KeyCode::End => self.select_position(self.order.len()),
+            _ => return false,
+        }
+        true
+    }
+}
+
+pub struct TableBuilder<S: TableSkin> {
+    table: Table<S>,
+}
+
+impl<S: TableSkin + 'static> Default for TableBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: TableSkin + 'static> TableBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            table: Table::new(),
+        }
+    }
+    pub fn add_column<T: Into<String>>(mut self, title: T, width: f32) -> Self {
+        self.table.add_column(title, width);
+        self
+    }
+    pub fn set_model<M: TableModel + 'static>(mut self, model: M) -> Self {
+        self.table.set_model(model);
+        self
+    }
+    pub fn sort_by(mut self, column: usize, sort_order: SortOrder) -> Self {
+        self.table.sort_by(Some((column, sort_order)));
+        self
+    }
+    pub fn on_selection_changed<F: Fn(&mut dyn Widget, usize) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.table.on_selection_changed(f);
+        self
+    }
+    pub fn build(self) -> Table<S> {
+        self.table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    const VISIBLE: usize = 3;
+
+    #[derive(Default, Debug)]
+    struct TestSkin;
+
+    impl EventHandlerProxy for TestSkin {}
+
+    impl TableSkin for TestSkin {
+        fn set_state(&mut self, _state: &TableState) {}
+        fn is_hot_area(&self, _x: f32, _y: f32) -> bool {
+            false
+        }
+        fn header_at(&self, _x: f32, _y: f32) -> Option<usize> {
+            None
+        }
+        fn divider_at(&self, _x: f32, _y: f32) -> Option<usize> {
+            None
+        }
+        fn row_at(&self, _x: f32, _y: f32) -> Option<usize> {
+            None
+        }
+        fn visible_count(&self) -> usize {
+            VISIBLE
+        }
+    }
+
+    fn model(rows: &[[&str; 2]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    fn table(rows: &[[&str; 2]]) -> Table<TestSkin> {
+        let mut table = Table::new();
+        table.add_column("name", 100.);
+        table.add_column("size", 100.);
+        table.set_model(model(rows));
+        table
+    }
+
+    #[test]
+    fn header_click_toggles_sort_order() {
+        let mut table = table(&[["b", "2"], ["a", "3"], ["c", "1"]]);
+        assert_eq!(table.order, vec![0, 1, 2]);
+        table.toggle_sort(0);
+        assert_eq!(table.get_sort(), Some((0, SortOrder::Ascending)));
+        assert_eq!(table.order, vec![1, 0, 2]);
+        table.toggle_sort(0);
+        assert_eq!(table.get_sort(), Some((0, SortOrder::Descending)));
+        assert_eq!(table.order, vec![2, 0, 1]);
+        table.toggle_sort(0);
+        assert_eq!(table.get_sort(), Some((0, SortOrder::Ascending)));
+        // another column starts ascending
+        table.toggle_sort(1);
+        assert_eq!(table.get_sort(), Some((1, SortOrder::Ascending)));
+        assert_eq!(table.order, vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn refresh_keeps_selected_model_row() {
+        let mut table = table(&[["b", "2"], ["a", "3"], ["c", "1"]]);
+        table.set_selection(Some(1));
+        table.sort_by(Some((0, SortOrder::Descending)));
+        assert_eq!(table.get_selection(), Some(1));
+        assert_eq!(table.selected_position(), Some(2));
+        table.model = Box::new(model(&[["b", "2"], ["a", "3"]]));
+        table.refresh();
+        assert_eq!(table.get_selection(), Some(1));
+        assert_eq!(table.selected_position(), Some(1));
+        // selected row is gone
+        table.model = Box::new(model(&[["b", "2"]]));
+        table.refresh();
+        assert_eq!(table.get_selection(), None);
+    }
+
+    #[test]
+    fn select_position_is_clamped_and_notifies_on_change() {
+        let mut table = table(&[["b", "2"], ["a", "3"], ["c", "1"]]);
+        table.sort_by(Some((0, SortOrder::Ascending)));
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        table.on_selection_changed({
+            let changes = changes.clone();
+            move |_, row| changes.lock().unwrap().push(row)
+        });
+        table.select_position(10);
+        assert_eq!(table.get_selection(), Some(2));
+        table.select_position(5);
+        table.select_position(0);
+        assert_eq!(table.get_selection(), Some(1));
+        assert_eq!(*changes.lock().unwrap(), vec![2, 1]);
+    }
+
+    #[test]
+    fn selection_is_scrolled_into_view() {
+        let rows: Vec<[&str; 2]> = (0..10).map(|_| ["x", "1"]).collect();
+        let mut table = table(&rows);
+        table.set_selection(Some(8));
+        assert_eq!(table.state.scroll, 6);
+        table.set_selection(Some(7));
+        assert_eq!(table.state.scroll, 6);
+        table.set_selection(Some(2));
+        assert_eq!(table.state.scroll, 2);
+        // scroll never goes past the last full page
+        table.state.scroll = 9;
+        table.set_selection(None);
+        assert_eq!(table.state.scroll, 10 - VISIBLE);
+    }
+}
@@ -0,0 +1,451 @@
+use crate::button::{Button, ButtonMode, ButtonSkin};
+use crate::overlay::OverlayId;
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::{Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+
+const HEADER_HEIGHT: f32 = 40.;
+const MAX_HEADER_WIDTH: f32 = 150.;
+
+struct Tab<B: ButtonSkin> {
+    header: Button<B>,
+    page: Box<dyn Widget>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TabsId(SrvId);
+
+impl From<TabsId> for SrvId {
+    fn from(v: TabsId) -> SrvId {
+        v.0
+    }
+}
+
+type TabChangedHandler = Box<dyn Fn(&mut dyn Widget, SrvId) + Send + Sync>;
+
+enum TabsOp {
+    AddTab(String, Box<dyn Widget>),
+    RemoveTab(SrvId),
+    Select(SrvId),
+    GetSelected,
+    SetTitle(SrvId, String),
+    OnTabChanged(TabChangedHandler),
+    RemoveOnTabChanged(usize),
+}
+
+impl Debug for TabsOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TabsOp::AddTab(title, page) => write!(f, "AddTab({:?}, {:?})", title, page),
+            TabsOp::RemoveTab(page) => write!(f, "RemoveTab({:?})", page),
+            TabsOp::Select(page) => write!(f, "Select({:?})", page),
+            TabsOp::GetSelected => write!(f, "GetSelected"),
+            TabsOp::SetTitle(page, title) => write!(f, "SetTitle({:?}, {:?})", page, title),
+            TabsOp::OnTabChanged(_) => write!(f, "OnTabChanged"),
+            TabsOp::RemoveOnTabChanged(handler_id) => {
+                write!(f, "RemoveOnTabChanged({:?})", handler_id)
+            }
+        }
+    }
+}
+
+impl TabsId {
+    pub async fn add_tab<T: Into<String>>(self, title: T, page: impl Widget + 'static) {
+        send_request(self.0, TabsOp::AddTab(title.into(), Box::new(page)))
+            .await
+            .unwrap()
+    }
+    /// Returns false if there is no such page
+    pub async fn remove_tab(self, page: SrvId) -> bool {
+        send_request(self.0, TabsOp::RemoveTab(page)).await.unwrap()
+    }
+    /// Returns false if there is no such page
+    pub async fn select(self, page: SrvId) -> bool {
+        send_request(self.0, TabsOp::Select(page)).await.unwrap()
+    }
+    pub async fn get_selected(self) -> Option<SrvId> {
+        send_request(self.0, TabsOp::GetSelected).await.unwrap()
+    }
+    pub async fn set_title<T: Into<String>>(self, page: SrvId, title: T) -> bool {
+        send_request(self.0, TabsOp::SetTitle(page, title.into()))
+            .await
+            .unwrap()
+    }
+    pub async fn on_tab_changed<F: Fn(&mut dyn Widget, SrvId) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, TabsOp::OnTabChanged(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_tab_changed(self, handler_id: usize) {
+        send_request(self.0, TabsOp::RemoveOnTabChanged(handler_id))
+            .await
+            .unwrap()
+    }
+}
+
+/// Header strip of radio-like buttons above the pages. Only selected page
+/// is drawn, receives input and is listed in `children`, but all pages are
+/// updated to keep serving their requests.
+pub struct Tabs<B: ButtonSkin> {
+    tabs: Vec<Tab<B>>,
+    selected: Option<usize>,
+    header_height: f32,
+    rect: Rect,
+    overlay: Option<OverlayId>,
+    reg: ServiceRegistration,
+    on_tab_changed_handlers: IndexMap<usize, TabChangedHandler>,
+}
+
+impl<B: ButtonSkin + 'static> Tabs<B> {
+    pub fn new() -> Self {
+        Self {
+            tabs: Vec::new(),
+            selected: None,
+            header_height: HEADER_HEIGHT,
+            rect: Rect::default(),
+            overlay: None,
+            reg: register_service(),
+            on_tab_changed_handlers: IndexMap::new(),
+        }
+    }
+    pub fn id(&self) -> TabsId {
+        TabsId(self.reg.id())
+    }
+    pub fn set_header_height(&mut self, header_height: f32) {
+        self.header_height = header_height;
+        self.update_widgets_rects();
+    }
+    pub fn get_header_height(&self) -> f32 {
+        self.header_height
+    }
+    pub fn add_tab_box(&mut self, title: String, mut page: Box<dyn Widget>) {
+        if let Some(overlay) = self.overlay {
+            page.attach_overlay(overlay)
+        }
+        let mut header = Button::new();
+        header.set_label(title);
+        header.set_mode(ButtonMode::Radio(false));
+        self.tabs.push(Tab { header, page });
+        if self.selected.is_none() {
+            self.select_index(self.tabs.len() - 1);
+        }
+        self.update_widgets_rects();
+    }
+    pub fn add_tab<T: Into<String>>(&mut self, title: T, page: impl Widget + 'static) {
+        self.add_tab_box(title.into(), Box::new(page))
+    }
+    pub fn remove_tab(&mut self, page: SrvId) -> Option<Box<dyn Widget>> {
+        let index = self.index_of(page)?;
+        let tab = self.tabs.remove(index);
+        self.selected = match self.selected {
+            _ if self.tabs.is_empty() => None,
+            Some(selected) if selected > index => Some(selected - 1),
+            Some(selected) if selected == index => Some(index.min(self.tabs.len() - 1)),
+            selected => selected,
+        };
+        if let Some(selected) = self.selected {
+            self.select_index(selected);
+        }
+        self.update_widgets_rects();
+        Some(tab.page)
+    }
+    pub fn select(&mut self, page: SrvId) -> bool {
+        match self.index_of(page) {
+            Some(index) => {
+                self.select_index(index);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn get_selected(&self) -> Option<SrvId> {
+        self.selected.map(|index| self.tabs[index].page.srv_id())
+    }
+    pub fn set_title(&mut self, page: SrvId, title: String) -> bool {
+        match self.index_of(page) {
+            Some(index) => {
+                self.tabs[index].header.set_label(title);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn on_tab_changed_box(&mut self, handler: TabChangedHandler) -> usize {
+        add_to_indexmap(&mut self.on_tab_changed_handlers, handler)
+    }
+    pub fn on_tab_changed<F: Fn(&mut dyn Widget, SrvId) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_tab_changed_box(Box::new(f))
+    }
+    pub fn remove_on_tab_changed(&mut self, handler_id: usize) {
+        self.on_tab_changed_handlers.remove(&handler_id);
+    }
+    fn index_of(&self, page: SrvId) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.page.srv_id() == page)
+    }
+    fn select_index(&mut self, index: usize) {
+        for (n, tab) in self.tabs.iter_mut().enumerate() {
+            tab.header.set_mode(ButtonMode::Radio(n == index));
+        }
+        self.selected = Some(index);
+    }
+    fn selected_page(&mut self) -> Option<&mut Box<dyn Widget>> {
+        let selected = self.selected?;
+        Some(&mut self.tabs[selected].page)
+    }
+    /// Header buttons switch themselves on click, find the one which did
+    fn check_headers(&mut self) {
+        let selected = self.selected;
+        let clicked = self.tabs.iter_mut().enumerate().position(|(n, tab)| {
+            Some(n) != selected && matches!(tab.header.get_mode(), ButtonMode::Radio(true))
+        });
+        if let Some(index) = clicked {
+            self.select_index(index);
+            let page = self.tabs[index].page.srv_id();
+            let handlers = std::mem::replace(&mut self.on_tab_changed_handlers, IndexMap::new());
+            for (_, handler) in &handlers {
+                handler(self, page);
+            }
+            self.on_tab_changed_handlers = handlers;
+        }
+    }
+    fn update_widgets_rects(&mut self) {
+        let rect = self.rect;
+        let header_height = self.header_height.min(rect.h);
+        if !self.tabs.is_empty() {
+            let header_width = (rect.w / self.tabs.len() as f32).min(MAX_HEADER_WIDTH);
+            let mut x = rect.x;
+            for tab in &mut self.tabs {
+                tab.header
+                    .set_rect(Rect::new(x, rect.y, header_width, header_height));
+                x += header_width;
+            }
+        }
+        let page_rect = Rect::new(
+            rect.x,
+            rect.y + header_height,
+            rect.w,
+            rect.h - header_height,
+        );
+        for tab in &mut self.tabs {
+            tab.page.set_rect(page_rect);
+        }
+    }
+}
+
+impl<B: ButtonSkin + 'static> Default for Tabs<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: ButtonSkin + 'static> Widget for Tabs<B> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.update_widgets_rects();
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.rect)
+    }
+    fn children(&self) -> Vec<&dyn Widget> {
+        let mut children: Vec<&dyn Widget> = self
+            .tabs
+            .iter()
+            .map(|tab| &tab.header as &dyn Widget)
+            .collect();
+        if let Some(selected) = self.selected {
+            children.push(self.tabs[selected].page.as_ref());
+        }
+        children
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        let selected = self.selected;
+        let mut children = Vec::new();
+        let mut page = None;
+        for (n, tab) in self.tabs.iter_mut().enumerate() {
+            children.push(&mut tab.header as &mut dyn Widget);
+            if Some(n) == selected {
+                page = Some(tab.page.as_mut() as &mut dyn Widget);
+            }
+        }
+        children.extend(page);
+        children
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("tabs", self.tabs.len().to_string()),
+            ("selected", format!("{:?}", self.selected)),
+        ]
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.overlay = Some(overlay);
+        for tab in &mut self.tabs {
+            tab.page.attach_overlay(overlay)
+        }
+    }
+}
+
+impl<B: ButtonSkin + 'static> EventHandlerProxy for Tabs<B> {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            TabsOp::AddTab(title, page) => {
+                self.add_tab_box(title, page);
+                Some(Box::new(()))
+            }
+            TabsOp::RemoveTab(page) => Some(Box::new(self.remove_tab(page).is_some())),
+            TabsOp::Select(page) => Some(Box::new(self.select(page))),
+            TabsOp::GetSelected => Some(Box::new(self.get_selected())),
+            TabsOp::SetTitle(page, title) => Some(Box::new(self.set_title(page, title))),
+            TabsOp::OnTabChanged(handler) => {
+                let handler_id = self.on_tab_changed_box(handler);
+                Some(Box::new(handler_id))
+            }
+            TabsOp::RemoveOnTabChanged(handler_id) => {
+                self.remove_on_tab_changed(handler_id);
+                Some(Box::new(()))
+            }
+        });
+        for tab in &mut self.tabs {
+            tab.header.update(ctx)?;
+            tab.page.update(ctx)?;
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        for tab in &mut self.tabs {
+            tab.header.draw(ctx)?;
+        }
+        if let Some(page) = self.selected_page() {
+            page.draw(ctx)?;
+        }
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        for tab in &mut self.tabs {
+            consumed |= tab.header.mouse_button_down_event(ctx, button, x, y)
+        }
+        if let Some(page) = self.selected_page() {
+            consumed |= page.mouse_button_down_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        for tab in &mut self.tabs {
+            consumed |= tab.header.mouse_button_up_event(ctx, button, x, y)
+        }
+        if let Some(page) = self.selected_page() {
+            consumed |= page.mouse_button_up_event(ctx, button, x, y)
+        }
+        self.check_headers();
+        consumed
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        let mut consumed = false;
+        for tab in &mut self.tabs {
+            consumed |= tab.header.mouse_motion_event(ctx, x, y, dx, dy)
+        }
+        if let Some(page) = self.selected_page() {
+            consumed |= page.mouse_motion_event(ctx, x, y, dx, dy)
+        }
+        consumed
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        match self.selected_page() {
+            Some(page) => page.mouse_wheel_event(ctx, x, y),
+            None => false,
+        }
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        match self.selected_page() {
+            Some(page) => page.key_down_event(ctx, keycode, keymods, repeat),
+            None => false,
+        }
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        match self.selected_page() {
+            Some(page) => page.key_up_event(ctx, keycode, keymods),
+            None => false,
+        }
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        match self.selected_page() {
+            Some(page) => page.text_input_event(ctx, character),
+            None => false,
+        }
+    }
+}
+
+pub struct TabsBuilder<B: ButtonSkin> {
+    tabs: Tabs<B>,
+}
+
+impl<B: ButtonSkin + 'static> Default for TabsBuilder<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: ButtonSkin + 'static> TabsBuilder<B> {
+    pub fn new() -> Self {
+        Self { tabs: Tabs::new() }
+    }
+    pub fn set_header_height(mut self, header_height: f32) -> Self {
+        self.tabs.set_header_height(header_height);
+        self
+    }
+    pub fn add_tab<T: Into<String>>(mut self, title: T, page: impl Widget + 'static) -> Self {
+        self.tabs.add_tab(title, page);
+        self
+    }
+    pub fn on_tab_changed<F: Fn(&mut dyn Widget, SrvId) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.tabs.on_tab_changed(f);
+        self
+    }
+    pub fn build(self) -> Tabs<B> {
+        self.tabs
+    }
+}
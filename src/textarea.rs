@@ -0,0 +1,558 @@
+use crate::textedit::TextEdit;
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{self, KeyCode, KeyMods};
+use ggez::input::mouse;
+use ggez::{Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+
+const TAB: &str = "    ";
+const UNDO_LIMIT: usize = 100;
+const WHEEL_LINES: f32 = 3.;
+
+#[derive(Clone, Default, Debug)]
+pub struct TextAreaState {
+    pub edit: TextEdit,
+    pub focused: bool,
+    pub rect: Rect,
+}
+
+/// Skin is responsible for wrapping text into lines and for vertical scrolling
+pub trait TextAreaSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &TextAreaState);
+    fn is_hot_area(&self, x: f32, y: f32) -> bool;
+    /// Index of the char boundary nearest to the point
+    fn char_index_at(&mut self, ctx: &mut Context, x: f32, y: f32) -> usize;
+    /// Index at the same horizontal position `lines` lines below (above if negative) `index`
+    fn char_index_by_lines(&mut self, ctx: &mut Context, index: usize, lines: isize) -> usize;
+    /// Start and end indices of the line containing `index`
+    fn line_bounds(&mut self, ctx: &mut Context, index: usize) -> (usize, usize);
+    /// Number of lines fitting into widget
+    fn page_lines(&self) -> usize;
+    fn scroll_by(&mut self, lines: isize);
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextAreaId(SrvId);
+
+impl From<TextAreaId> for SrvId {
+    fn from(v: TextAreaId) -> SrvId {
+        v.0
+    }
+}
+
+type TextHandler = Box<dyn Fn(&mut dyn Widget, &str) + Send + Sync>;
+
+enum TextAreaOp {
+    GetText,
+    SetText(String),
+    Undo,
+    Redo,
+    OnChange(TextHandler),
+    RemoveOnChange(usize),
+}
+
+impl Debug for TextAreaOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextAreaOp::GetText => write!(f, "GetText"),
+            TextAreaOp::SetText(text) => write!(f, "SetText({:?})", text),
+            TextAreaOp::Undo => write!(f, "Undo"),
+            TextAreaOp::Redo => write!(f, "Redo"),
+            TextAreaOp::OnChange(_) => write!(f, "OnChange"),
+            TextAreaOp::RemoveOnChange(handler_id) => {
+                write!(f, "RemoveOnChange({:?})", handler_id)
+            }
+        }
+    }
+}
+
+impl TextAreaId {
+    pub async fn get_text(self) -> String {
+        send_request(self.0, TextAreaOp::GetText).await.unwrap()
+    }
+    pub async fn set_text(self, text: String) {
+        send_request(self.0, TextAreaOp::SetText(text))
+            .await
+            .unwrap()
+    }
+    pub async fn undo(self) -> bool {
+        send_request(self.0, TextAreaOp::Undo).await.unwrap()
+    }
+    pub async fn redo(self) -> bool {
+        send_request(self.0, TextAreaOp::Redo).await.unwrap()
+    }
+    pub async fn on_change<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, TextAreaOp::OnChange(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_change(self, handler_id: usize) {
+        send_request(self.0, TextAreaOp::RemoveOnChange(handler_id))
+            .await
+            .unwrap()
+    }
+}
+
+/// Kind of the last edit, consecutive edits of the same kind are undone at once
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum EditKind {
+    None,
+    Typing,
+    Deleting,
+    Other,
+}
+
+pub struct TextArea<S: TextAreaSkin> {
+    state: TextAreaState,
+    skin: S,
+    reg: ServiceRegistration,
+    dragging: bool,
+    undo_stack: Vec<TextEdit>,
+    redo_stack: Vec<TextEdit>,
+    last_edit: EditKind,
+    on_change_handlers: IndexMap<usize, TextHandler>,
+}
+
+impl<S: TextAreaSkin> TextArea<S> {
+    pub fn new() -> Self {
+        Self {
+            state: TextAreaState::default(),
+            skin: S::default(),
+            reg: register_service(),
+            dragging: false,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            last_edit: EditKind::None,
+            on_change_handlers: IndexMap::new(),
+        }
+    }
+    pub fn id(&self) -> TextAreaId {
+        TextAreaId(self.reg.id())
+    }
+    /// Replaces the text and clears undo history
+    pub fn set_text(&mut self, text: String) {
+        self.state.edit.set_text(text);
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_edit = EditKind::None;
+    }
+    pub fn get_text(&self) -> &str {
+        self.state.edit.text.as_str()
+    }
+    pub fn is_focused(&self) -> bool {
+        self.state.focused
+    }
+    pub fn on_change_box(&mut self, handler: TextHandler) -> usize {
+        add_to_indexmap(&mut self.on_change_handlers, handler)
+    }
+    pub fn on_change<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_change_box(Box::new(f))
+    }
+    pub fn remove_on_change(&mut self, handler_id: usize) {
+        self.on_change_handlers.remove(&handler_id);
+    }
+}
+
+impl<S: TextAreaSkin> Default for TextArea<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: TextAreaSkin + 'static> TextArea<S> {
+    fn fire_on_change(&mut self) {
+        let text = self.state.edit.text.clone();
+        let handlers = std::mem::replace(&mut self.on_change_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, &text);
+        }
+        self.on_change_handlers = handlers;
+    }
+    fn apply_edit<F: FnOnce(&mut TextEdit) -> bool>(&mut self, kind: EditKind, f: F) -> bool {
+        let before = self.state.edit.clone();
+        if !f(&mut self.state.edit) {
+            return false;
+        }
+        if kind != self.last_edit || kind == EditKind::Other {
+            self.undo_stack.push(before);
+            if self.undo_stack.len() > UNDO_LIMIT {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+        self.last_edit = kind;
+        self.fire_on_change();
+        true
+    }
+    pub fn undo(&mut self) -> bool {
+        if let Some(edit) = self.undo_stack.pop() {
+            let current = std::mem::replace(&mut self.state.edit, edit);
+            self.redo_stack.push(current);
+            self.last_edit = EditKind::None;
+            self.fire_on_change();
+            true
+        } else {
+            false
+        }
+    }
+    pub fn redo(&mut self) -> bool {
+        if let Some(edit) = self.redo_stack.pop() {
+            let current = std::mem::replace(&mut self.state.edit, edit);
+            self.undo_stack.push(current);
+            self.last_edit = EditKind::None;
+            self.fire_on_change();
+            true
+        } else {
+            false
+        }
+    }
+    fn move_caret(&mut self, pos: usize, select: bool) {
+        self.state.edit.move_caret(pos, select);
+        self.last_edit = EditKind::None;
+    }
+}
+
+impl<S: TextAreaSkin + 'static> Widget for TextArea<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("length", self.state.edit.len().to_string()),
+            ("focused", self.state.focused.to_string()),
+        ]
+    }
+}
+
+impl<S: TextAreaSkin + 'static> EventHandlerProxy for TextArea<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            TextAreaOp::GetText => Some(Box::new(self.get_text().to_string())),
+            TextAreaOp::SetText(text) => {
+                self.set_text(text);
+                Some(Box::new(()))
+            }
+            TextAreaOp::Undo => Some(Box::new(self.undo())),
+            TextAreaOp::Redo => Some(Box::new(self.redo())),
+            TextAreaOp::OnChange(handler) => Some(Box::new(self.on_change_box(handler))),
+            TextAreaOp::RemoveOnChange(handler_id) => {
+                self.remove_on_change(handler_id);
+                Some(Box::new(()))
+            }
+        });
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button != MouseButton::Left {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        if self.skin.is_hot_area(x, y) {
+            let pos = self.skin.char_index_at(ctx, x, y);
+            let select = keyboard::active_mods(ctx).contains(KeyMods::SHIFT);
+            self.move_caret(pos, select);
+            self.state.focused = true;
+            self.dragging = true;
+            true
+        } else {
+            self.state.focused = false;
+            self.state.edit.anchor = None;
+            false
+        }
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> bool {
+        if button == MouseButton::Left && self.dragging {
+            self.dragging = false;
+            return true;
+        }
+        false
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> bool {
+        if self.dragging {
+            let pos = self.skin.char_index_at(ctx, x, y);
+            self.move_caret(pos, true);
+            return true;
+        }
+        false
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) -> bool {
+        let pos = mouse::position(ctx);
+        if self.skin.is_hot_area(pos.x, pos.y) {
+            self.skin.scroll_by((-y * WHEEL_LINES) as isize);
+            return true;
+        }
+        false
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        if !self.state.focused {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        let select = keymods.contains(KeyMods::SHIFT);
+        let ctrl = keymods.contains(KeyMods::CTRL);
+        let caret = self.state.edit.caret;
+        let page = self.skin.page_lines() as isize;
+        match keycode {
+            KeyCode::Left => {
+                self.state.edit.move_left(select);
+                self.last_edit = EditKind::None;
+            }
+            KeyCode::Right => {
+                self.state.edit.move_right(select);
+                self.last_edit = EditKind::None;
+            }
+            KeyCode::Up => {
+                let pos = self.skin.char_index_by_lines(ctx, caret, -1);
+                self.move_caret(pos, select)
+            }
+            KeyCode::Down => {
+                let pos = self.skin.char_index_by_lines(ctx, caret, 1);
+                self.move_caret(pos, select)
+            }
+            KeyCode::PageUp => {
+                let pos = self.skin.char_index_by_lines(ctx, caret, -page);
+                self.move_caret(pos, select)
+            }
+            KeyCode::PageDown => {
+                let pos = self.skin.char_index_by_lines(ctx, caret, page);
+                self.move_caret(pos, select)
+            }
+            KeyCode::Home if ctrl => self.move_caret(0, select),
+            KeyCode::End if ctrl => {
+                let len = self.state.edit.len();
+                self.move_caret(len, select)
+            }
+            KeyCode::Home => {
+                let (start, _) = self.skin.line_bounds(ctx, caret);
+                self.move_caret(start, select)
+            }
+            KeyCode::End => {
+                let (_, end) = self.skin.line_bounds(ctx, caret);
+                self.move_caret(end, select)
+            }
+            KeyCode::A if ctrl => self.state.edit.select_all(),
+            KeyCode::Z if ctrl && select => {
+                self.redo();
+            }
+            KeyCode::Z if ctrl => {
+                self.undo();
+            }
+            KeyCode::Y if ctrl => {
+                self.redo();
+            }
+            KeyCode::Back => {
+                self.apply_edit(EditKind::Deleting, |edit| edit.delete_backward());
+            }
+            KeyCode::Delete => {
+                self.apply_edit(EditKind::Deleting, |edit| edit.delete_forward());
+            }
+            KeyCode::Return | KeyCode::NumpadEnter => {
+                self.apply_edit(EditKind::Other, |edit| edit.insert("\n", None));
+            }
+            KeyCode::Tab => {
+                self.apply_edit(EditKind::Other, |edit| edit.insert(TAB, None));
+            }
+            KeyCode::Escape => {
+                self.state.focused = false;
+                self.state.edit.anchor = None;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) -> bool {
+        if !self.state.focused {
+            return false;
+        }
+        if !character.is_control() {
+            self.apply_edit(EditKind::Typing, |edit| {
+                edit.insert(character.encode_utf8(&mut [0; 4]), None)
+            });
+        }
+        true
+    }
+}
+
+pub struct TextAreaBuilder<S: TextAreaSkin> {
+    text_area: TextArea<S>,
+}
+
+impl<S: TextAreaSkin> Default for TextAreaBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: TextAreaSkin> TextAreaBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            text_area: TextArea::new(),
+        }
+    }
+    pub fn set_text<T: Into<String>>(mut self, text: T) -> Self {
+        self.text_area.set_text(text.into());
+        self
+    }
+    pub fn on_change<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.text_area.on_change(f);
+        self
+    }
+    pub fn build(self) -> TextArea<S> {
+        self.text_area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default, Debug)]
+    struct TestSkin;
+
+    impl EventHandlerProxy for TestSkin {}
+
+    impl TextAreaSkin for TestSkin {
+        fn set_state(&mut self, _state: &TextAreaState) {}
+        fn is_hot_area(&self, _x: f32, _y: f32) -> bool {
+            false
+        }
+        fn char_index_at(&mut self, _ctx: &mut Context, _x: f32, _y: f32) -> usize {
+            0
+        }
+        fn char_index_by_lines(
+            &mut self,
+            _ctx: &mut Context,
+            index: usize,
+            _lines: isize,
+        ) -> usize {
+            index
+        }
+        fn line_bounds(&mut self, _ctx: &mut Context, index: usize) -> (usize, usize) {
+            (index, index)
+        }
+        fn page_lines(&self) -> usize {
+            1
+        }
+        fn scroll_by(&mut self, _lines: isize) {}
+    }
+
+    fn type_text(text_area: &mut TextArea<TestSkin>, s: &str) {
+        for c in s.chars() {
+            text_area.apply_edit(EditKind::Typing, |edit| edit.insert(&c.to_string(), None));
+        }
+    }
+
+    #[test]
+    fn edits_of_same_kind_are_undone_together() {
+        let mut text_area = TextArea::<TestSkin>::new();
+        type_text(&mut text_area, "abc");
+        text_area.apply_edit(EditKind::Deleting, |edit| edit.delete_backward());
+        text_area.apply_edit(EditKind::Deleting, |edit| edit.delete_backward());
+        type_text(&mut text_area, "xy");
+        assert_eq!(text_area.get_text(), "axy");
+        assert!(text_area.undo());
+        assert_eq!(text_area.get_text(), "a");
+        assert!(text_area.undo());
+        assert_eq!(text_area.get_text(), "abc");
+        assert!(text_area.undo());
+        assert_eq!(text_area.get_text(), "");
+        assert!(!text_area.undo());
+        assert!(text_area.redo());
+        assert!(text_area.redo());
+        assert_eq!(text_area.get_text(), "a");
+        // new edit drops the redo history
+        type_text(&mut text_area, "z");
+        assert!(!text_area.redo());
+        assert_eq!(text_area.get_text(), "az");
+    }
+
+    #[test]
+    fn caret_move_and_other_edits_break_groups() {
+        let mut text_area = TextArea::<TestSkin>::new();
+        type_text(&mut text_area, "ab");
+        text_area.move_caret(0, false);
+        type_text(&mut text_area, "c");
+        text_area.apply_edit(EditKind::Other, |edit| edit.insert("\n", None));
+        text_area.apply_edit(EditKind::Other, |edit| edit.insert("\n", None));
+        assert_eq!(text_area.get_text(), "c\n\nab");
+        let mut history = Vec::new();
+        while text_area.undo() {
+            history.push(text_area.get_text().to_string());
+        }
+        assert_eq!(history, vec!["c\nab", "cab", "ab", ""]);
+    }
+
+    #[test]
+    fn undo_history_is_limited() {
+        let mut text_area = TextArea::<TestSkin>::new();
+        for _ in 0..UNDO_LIMIT + 5 {
+            text_area.apply_edit(EditKind::Other, |edit| edit.insert("a", None));
+        }
+        assert_eq!(text_area.undo_stack.len(), UNDO_LIMIT);
+        while text_area.undo() {}
+        // the oldest edits can't be undone anymore
+        assert_eq!(text_area.get_text(), "aaaaa");
+    }
+
+    #[test]
+    fn set_text_clears_history() {
+        let mut text_area = TextArea::<TestSkin>::new();
+        type_text(&mut text_area, "ab");
+        text_area.set_text("new".to_string());
+        assert!(!text_area.undo());
+        assert_eq!(text_area.get_text(), "new");
+    }
+}
@@ -0,0 +1,224 @@
+/// Text with caret and selection shared by text editing widgets.
+/// Caret and selection anchor are char (not byte) indices.
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct TextEdit {
+    pub text: String,
+    pub caret: usize,
+    pub anchor: Option<usize>,
+}
+
+pub fn char_count(s: &str) -> usize {
+    s.chars().count()
+}
+
+pub fn byte_index(s: &str, char_index: usize) -> usize {
+    s.char_indices()
+        .nth(char_index)
+        .map(|(n, _)| n)
+        .unwrap_or_else(|| s.len())
+}
+
+pub fn char_slice(s: &str, start: usize, end: usize) -> &str {
+    &s[byte_index(s, start)..byte_index(s, end)]
+}
+
+impl TextEdit {
+    pub fn new(text: String) -> Self {
+        let caret = char_count(&text);
+        Self {
+            text,
+            caret,
+            anchor: None,
+        }
+    }
+    pub fn len(&self) -> usize {
+        char_count(&self.text)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+    pub fn set_text(&mut self, text: String) {
+        *self = Self::new(text)
+    }
+    /// Ordered selection bounds, `None` if nothing is selected
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        match self.anchor {
+            Some(anchor) if anchor < self.caret => Some((anchor, self.caret)),
+            Some(anchor) if anchor > self.caret => Some((self.caret, anchor)),
+            _ => None,
+        }
+    }
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some((start, end)) => char_slice(&self.text, start, end),
+            None => "",
+        }
+    }
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.caret = self.len();
+    }
+    /// Moves caret, extending selection if `select` is set and dropping it otherwise
+    pub fn move_caret(&mut self, pos: usize, select: bool) {
+        let pos = pos.min(self.len());
+        if select {
+            if self.anchor.is_none() {
+                self.anchor = Some(self.caret)
+            }
+        } else {
+            self.anchor = None
+        }
+        self.caret = pos;
+    }
+    pub fn move_left(&mut self, select: bool) {
+        match self.selection() {
+            Some((start, _)) if !select => self.move_caret(start, false),
+            _ => self.move_caret(self.caret.saturating_sub(1), select),
+        }
+    }
+    pub fn move_right(&mut self, select: bool) {
+        match self.selection() {
+            Some((_, end)) if !select => self.move_caret(end, false),
+            _ => self.move_caret(self.caret + 1, select),
+        }
+    }
+    /// Replaces selection (or inserts at caret) with the string, no more than
+    /// `max_length` chars in resulting text. Returns true if text was changed.
+    pub fn insert(&mut self, s: &str, max_length: Option<usize>) -> bool {
+        let (start, end) = self.selection().unwrap_or((self.caret, self.caret));
+        let mut s = s.to_string();
+        if let Some(max_length) = max_length {
+            let room = max_length.saturating_sub(self.len() - (end - start));
+            s = s.chars().take(room).collect();
+        }
+        if s.is_empty() && start == end {
+            return false;
+        }
+        let (bstart, bend) = (byte_index(&self.text, start), byte_index(&self.text, end));
+        self.text.replace_range(bstart..bend, &s);
+        self.caret = start + char_count(&s);
+        self.anchor = None;
+        true
+    }
+    pub fn delete_selection(&mut self) -> bool {
+        if self.selection().is_some() {
+            self.insert("", None)
+        } else {
+            false
+        }
+    }
+    /// Backspace
+    pub fn delete_backward(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        if self.caret == 0 {
+            return false;
+        }
+        self.anchor = Some(self.caret - 1);
+        self.delete_selection()
+    }
+    /// Delete
+    pub fn delete_forward(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        if self.caret >= self.len() {
+            return false;
+        }
+        self.anchor = Some(self.caret + 1);
+        self.delete_selection()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(text: &str, caret: usize, anchor: Option<usize>) -> TextEdit {
+        TextEdit {
+            text: text.to_string(),
+            caret,
+            anchor,
+        }
+    }
+
+    #[test]
+    fn indices_are_chars_not_bytes() {
+        let s = "aπ€b";
+        assert_eq!(char_count(s), 4);
+        assert_eq!(byte_index(s, 0), 0);
+        assert_eq!(byte_index(s, 2), 3);
+        assert_eq!(byte_index(s, 3), 6);
+        assert_eq!(byte_index(s, 4), s.len());
+        assert_eq!(byte_index(s, 10), s.len());
+        assert_eq!(char_slice(s, 1, 3), "π€");
+        assert_eq!(char_slice(s, 3, 4), "b");
+        let e = edit(s, 1, Some(3));
+        assert_eq!(e.selected_text(), "π€");
+        assert_eq!(e.len(), 4);
+    }
+
+    #[test]
+    fn insert_replaces_selection_within_max_length() {
+        let mut e = edit("abcd", 3, Some(1));
+        // two selected chars are replaced, so five chars fit into seven
+        assert!(e.insert("ππππππ", Some(7)));
+        assert_eq!(e.text, "aπππππd");
+        assert_eq!((e.caret, e.anchor), (6, None));
+        assert!(!e.insert("x", Some(7)));
+        assert_eq!(e.text, "aπππππd");
+        // with no room the selection is still deleted
+        let mut e = edit("abcd", 2, Some(1));
+        assert!(e.insert("xy", Some(4)));
+        assert_eq!(e.text, "axcd");
+        let mut e = edit("abcd", 2, Some(1));
+        assert!(e.insert("", Some(4)));
+        assert_eq!((e.text.as_str(), e.caret), ("acd", 1));
+    }
+
+    #[test]
+    fn delete_at_edges_and_with_selection() {
+        let mut e = edit("aπb", 0, None);
+        assert!(!e.delete_backward());
+        assert!(e.delete_forward());
+        assert_eq!((e.text.as_str(), e.caret), ("πb", 0));
+        let mut e = edit("aπb", 3, None);
+        assert!(!e.delete_forward());
+        assert!(e.delete_backward());
+        assert_eq!((e.text.as_str(), e.caret), ("aπ", 2));
+        assert!(e.delete_backward());
+        assert_eq!((e.text.as_str(), e.caret), ("a", 1));
+        // selection is deleted instead of the char next to the caret
+        let mut e = edit("aπbc", 1, Some(3));
+        assert!(e.delete_backward());
+        assert_eq!((e.text.as_str(), e.caret, e.anchor), ("ac", 1, None));
+        let mut e = edit("aπbc", 3, Some(1));
+        assert!(e.delete_forward());
+        assert_eq!((e.text.as_str(), e.caret), ("ac", 1));
+    }
+
+    #[test]
+    fn moving_without_shift_collapses_selection() {
+        let mut e = edit("abcdef", 4, Some(1));
+        e.move_left(false);
+        assert_eq!((e.caret, e.anchor), (1, None));
+        let mut e = edit("abcdef", 1, Some(4));
+        e.move_right(false);
+        assert_eq!((e.caret, e.anchor), (4, None));
+        // with shift the selection is extended from the anchor
+        let mut e = edit("abcdef", 4, Some(1));
+        e.move_right(true);
+        assert_eq!((e.caret, e.anchor), (5, Some(1)));
+        e.move_left(true);
+        e.move_left(true);
+        assert_eq!(e.selection(), Some((1, 3)));
+        // at the edges the caret stays
+        let mut e = edit("ab", 0, None);
+        e.move_left(false);
+        assert_eq!(e.caret, 0);
+        e.move_caret(2, false);
+        e.move_right(false);
+        assert_eq!(e.caret, 2);
+    }
+}
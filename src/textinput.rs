@@ -0,0 +1,435 @@
+use crate::textedit::TextEdit;
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::{Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+
+#[derive(Clone, Default, Debug)]
+pub struct TextInputState {
+    pub edit: TextEdit,
+    pub placeholder: String,
+    pub max_length: Option<usize>,
+    pub focused: bool,
+    pub rect: Rect,
+}
+
+pub trait TextInputSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &TextInputState);
+    fn is_hot_area(&self, x: f32, y: f32) -> bool;
+    /// Index of the char boundary nearest to the point
+    fn char_index_at(&mut self, ctx: &mut Context, x: f32, y: f32) -> usize;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextInputId(SrvId);
+
+impl From<TextInputId> for SrvId {
+    fn from(v: TextInputId) -> SrvId {
+        v.0
+    }
+}
+
+type TextHandler = Box<dyn Fn(&mut dyn Widget, &str) + Send + Sync>;
+
+enum TextInputOp {
+    GetText,
+    SetText(String),
+    SetPlaceholder(String),
+    SetMaxLength(Option<usize>),
+    OnChange(TextHandler),
+    RemoveOnChange(usize),
+    OnSubmit(TextHandler),
+    RemoveOnSubmit(usize),
+}
+
+impl Debug for TextInputOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextInputOp::GetText => write!(f, "GetText"),
+            TextInputOp::SetText(text) => write!(f, "SetText({:?})", text),
+            TextInputOp::SetPlaceholder(text) => write!(f, "SetPlaceholder({:?})", text),
+            TextInputOp::SetMaxLength(max_length) => write!(f, "SetMaxLength({:?})", max_length),
+            TextInputOp::OnChange(_) => write!(f, "OnChange"),
+            TextInputOp::RemoveOnChange(handler_id) => {
+                write!(f, "RemoveOnChange({:?})", handler_id)
+            }
+            TextInputOp::OnSubmit(_) => write!(f, "OnSubmit"),
+            TextInputOp::RemoveOnSubmit(handler_id) => {
+                write!(f, "RemoveOnSubmit({:?})", handler_id)
+            }
+        }
+    }
+}
+
+impl TextInputId {
+    pub async fn get_text(self) -> String {
+        send_request(self.0, TextInputOp::GetText).await.unwrap()
+    }
+    pub async fn set_text(self, text: String) {
+        send_request(self.0, TextInputOp::SetText(text))
+            .await
+            .unwrap()
+    }
+    pub async fn set_placeholder(self, text: String) {
+        send_request(self.0, TextInputOp::SetPlaceholder(text))
+            .await
+            .unwrap()
+    }
+    pub async fn set_max_length(self, max_length: Option<usize>) {
+        send_request(self.0, TextInputOp::SetMaxLength(max_length))
+            .await
+            .unwrap()
+    }
+    pub async fn on_change<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, TextInputOp::OnChange(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_change(self, handler_id: usize) {
+        send_request(self.0, TextInputOp::RemoveOnChange(handler_id))
+            .await
+            .unwrap()
+    }
+    pub async fn on_submit<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, TextInputOp::OnSubmit(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_submit(self, handler_id: usize) {
+        send_request(self.0, TextInputOp::RemoveOnSubmit(handler_id))
+            .await
+            .unwrap()
+    }
+}
+
+pub struct TextInput<S: TextInputSkin> {
+    state: TextInputState,
+    skin: S,
+    reg: ServiceRegistration,
+    dragging: bool,
+    on_change_handlers: IndexMap<usize, TextHandler>,
+    on_submit_handlers: IndexMap<usize, TextHandler>,
+}
+
+impl<S: TextInputSkin> TextInput<S> {
+    pub fn new() -> Self {
+        Self {
+            state: TextInputState::default(),
+            skin: S::default(),
+            reg: register_service(),
+            dragging: false,
+            on_change_handlers: IndexMap::new(),
+            on_submit_handlers: IndexMap::new(),
+        }
+    }
+    pub fn id(&self) -> TextInputId {
+        TextInputId(self.reg.id())
+    }
+    pub fn set_text(&mut self, text: String) {
+        let text = match self.state.max_length {
+            Some(max_length) => text.chars().take(max_length).collect(),
+            None => text,
+        };
+        self.state.edit.set_text(text)
+    }
+    pub fn get_text(&self) -> &str {
+        self.state.edit.text.as_str()
+    }
+    pub fn set_placeholder(&mut self, text: String) {
+        self.state.placeholder = text
+    }
+    pub fn get_placeholder(&self) -> &str {
+        self.state.placeholder.as_str()
+    }
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.state.max_length = max_length;
+        let text = self.state.edit.text.clone();
+        self.set_text(text);
+    }
+    pub fn get_max_length(&self) -> Option<usize> {
+        self.state.max_length
+    }
+    pub fn is_focused(&self) -> bool {
+        self.state.focused
+    }
+    pub fn on_change_box(&mut self, handler: TextHandler) -> usize {
+        add_to_indexmap(&mut self.on_change_handlers, handler)
+    }
+    pub fn on_change<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_change_box(Box::new(f))
+    }
+    pub fn remove_on_change(&mut self, handler_id: usize) {
+        self.on_change_handlers.remove(&handler_id);
+    }
+    pub fn on_submit_box(&mut self, handler: TextHandler) -> usize {
+        add_to_indexmap(&mut self.on_submit_handlers, handler)
+    }
+    pub fn on_submit<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_submit_box(Box::new(f))
+    }
+    pub fn remove_on_submit(&mut self, handler_id: usize) {
+        self.on_submit_handlers.remove(&handler_id);
+    }
+}
+
+impl<S: TextInputSkin> Default for TextInput<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: TextInputSkin + 'static> TextInput<S> {
+    fn fire_on_change(&mut self) {
+        let text = self.state.edit.text.clone();
+        let handlers = std::mem::replace(&mut self.on_change_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, &text);
+        }
+        self.on_change_handlers = handlers;
+    }
+    fn fire_on_submit(&mut self) {
+        let text = self.state.edit.text.clone();
+        let handlers = std::mem::replace(&mut self.on_submit_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, &text);
+        }
+        self.on_submit_handlers = handlers;
+    }
+}
+
+impl<S: TextInputSkin + 'static> Widget for TextInput<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("text", self.state.edit.text.clone()),
+            ("focused", self.state.focused.to_string()),
+        ]
+    }
+}
+
+impl<S: TextInputSkin + 'static> EventHandlerProxy for TextInput<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            TextInputOp::GetText => Some(Box::new(self.get_text().to_string())),
+            TextInputOp::SetText(text) => {
+                self.set_text(text);
+                Some(Box::new(()))
+            }
+            TextInputOp::SetPlaceholder(text) => {
+                self.set_placeholder(text);
+                Some(Box::new(()))
+            }
+            TextInputOp::SetMaxLength(max_length) => {
+                self.set_max_length(max_length);
+                Some(Box::new(()))
+            }
+            TextInputOp::OnChange(handler) => Some(Box::new(self.on_change_box(handler))),
+            TextInputOp::RemoveOnChange(handler_id) => {
+                self.remove_on_change(handler_id);
+                Some(Box::new(()))
+            }
+            TextInputOp::OnSubmit(handler) => Some(Box::new(self.on_submit_box(handler))),
+            TextInputOp::RemoveOnSubmit(handler_id) => {
+                self.remove_on_submit(handler_id);
+                Some(Box::new(()))
+            }
+        });
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button != MouseButton::Left {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        if self.skin.is_hot_area(x, y) {
+            let pos = self.skin.char_index_at(ctx, x, y);
+            let select = ggez::input::keyboard::active_mods(ctx).contains(KeyMods::SHIFT);
+            self.state.edit.move_caret(pos, select);
+            self.state.focused = true;
+            self.dragging = true;
+            true
+        } else {
+            self.state.focused = false;
+            self.state.edit.anchor = None;
+            false
+        }
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) -> bool {
+        if button == MouseButton::Left && self.dragging {
+            self.dragging = false;
+            return true;
+        }
+        false
+    }
+
+    fn mouse_motion_event(
+        &mut self,
+        ctx: &mut Context,
+        x: f32,
+        y: f32,
+        _dx: f32,
+        _dy: f32,
+    ) -> bool {
+        if self.dragging {
+            let pos = self.skin.char_index_at(ctx, x, y);
+            self.state.edit.move_caret(pos, true);
+            return true;
+        }
+        false
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        if !self.state.focused {
+            return false;
+        }
+        let select = keymods.contains(KeyMods::SHIFT);
+        let edit = &mut self.state.edit;
+        let changed = match keycode {
+            KeyCode::Left => {
+                edit.move_left(select);
+                false
+            }
+            KeyCode::Right => {
+                edit.move_right(select);
+                false
+            }
+            KeyCode::Home => {
+                edit.move_caret(0, select);
+                false
+            }
+            KeyCode::End => {
+                let len = edit.len();
+                edit.move_caret(len, select);
+                false
+            }
+            KeyCode::A if keymods.contains(KeyMods::CTRL) => {
+                edit.select_all();
+                false
+            }
+            KeyCode::Back => edit.delete_backward(),
+            KeyCode::Delete => edit.delete_forward(),
+            KeyCode::Return | KeyCode::NumpadEnter => {
+                self.fire_on_submit();
+                return true;
+            }
+            KeyCode::Escape => {
+                self.state.focused = false;
+                self.state.edit.anchor = None;
+                return true;
+            }
+            _ => return false,
+        };
+        if changed {
+            self.fire_on_change();
+        }
+        true
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) -> bool {
+        if !self.state.focused {
+            return false;
+        }
+        if !character.is_control() {
+            let max_length = self.state.max_length;
+            if self
+                .state
+                .edit
+                .insert(character.encode_utf8(&mut [0; 4]), max_length)
+            {
+                self.fire_on_change();
+            }
+        }
+        true
+    }
+}
+
+pub struct TextInputBuilder<S: TextInputSkin> {
+    text_input: TextInput<S>,
+}
+
+impl<S: TextInputSkin> Default for TextInputBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: TextInputSkin> TextInputBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            text_input: TextInput::new(),
+        }
+    }
+    pub fn set_text<T: Into<String>>(mut self, text: T) -> Self {
+        self.text_input.set_text(text.into());
+        self
+    }
+    pub fn set_placeholder<T: Into<String>>(mut self, text: T) -> Self {
+        self.text_input.set_placeholder(text.into());
+        self
+    }
+    pub fn set_max_length(mut self, max_length: usize) -> Self {
+        self.text_input.set_max_length(Some(max_length));
+        self
+    }
+    pub fn on_change<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.text_input.on_change(f);
+        self
+    }
+    pub fn on_submit<F: Fn(&mut dyn Widget, &str) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.text_input.on_submit(f);
+        self
+    }
+    pub fn build(self) -> TextInput<S> {
+        self.text_input
+    }
+}
@@ -0,0 +1,132 @@
+use crate::overlay::{place_popup, Placement};
+use crate::Widget;
+use async_call::SrvId;
+use ggez::graphics::{
+    self, Align, Color, DrawMode, DrawParam, MeshBuilder, Rect, Scale, Text, TextFragment,
+};
+use ggez::input::mouse;
+use ggez::nalgebra::Point2;
+use ggez::{timer, Context, GameResult};
+use std::time::Duration;
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(500);
+const TEXT_SCALE: f32 = 14.;
+const MAX_WIDTH: f32 = 300.;
+const PADDING: f32 = 4.;
+/// Space left for the mouse pointer below the hot spot
+const CURSOR_SIZE: f32 = 20.;
+const BACKGROUND: Color = Color::new(1., 1., 0.85, 1.);
+const TEXT_COLOR: Color = Color::new(0., 0., 0., 1.);
+
+struct Hover {
+    owner: SrvId,
+    text: String,
+    since: Duration,
+    x: f32,
+    y: f32,
+}
+
+/// Shows tooltip of the widget under the mouse when it stays over the widget
+/// for the delay. Draw it above the root widget and the overlay.
+pub struct Tooltips {
+    delay: Duration,
+    hover: Option<Hover>,
+    /// Mouse press hides the tooltip until the mouse leaves the widget
+    suppressed: bool,
+}
+
+impl Default for Tooltips {
+    fn default() -> Self {
+        Self {
+            delay: DEFAULT_DELAY,
+            hover: None,
+            suppressed: false,
+        }
+    }
+}
+
+impl Tooltips {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay
+    }
+    pub fn get_delay(&self) -> Duration {
+        self.delay
+    }
+    pub fn hide(&mut self) {
+        self.suppressed = true
+    }
+    /// Tracks the widget with tooltip under the mouse in the subtree
+    pub fn update(&mut self, ctx: &Context, root: &dyn Widget) {
+        let mouse = mouse::position(ctx);
+        let owner = match root.tooltip_owner_at(mouse.x, mouse.y) {
+            Some(owner) => owner,
+            None => {
+                self.hover = None;
+                self.suppressed = false;
+                return;
+            }
+        };
+        let text = owner.tooltip().unwrap_or_default();
+        match &mut self.hover {
+            Some(hover) if hover.owner == owner.srv_id() => {
+                if hover.text != text {
+                    hover.text = text.to_string()
+                }
+                // follows the mouse until shown, then stays in place
+                if timer::time_since_start(ctx) < hover.since + self.delay {
+                    hover.x = mouse.x;
+                    hover.y = mouse.y;
+                }
+            }
+            _ => {
+                self.hover = Some(Hover {
+                    owner: owner.srv_id(),
+                    text: text.to_string(),
+                    since: timer::time_since_start(ctx),
+                    x: mouse.x,
+                    y: mouse.y,
+                });
+                self.suppressed = false;
+            }
+        }
+    }
+
+    /// Tooltip is placed below the mouse pointer and moved inside the bounds
+    pub fn draw(&self, ctx: &mut Context, bounds: Rect) -> GameResult {
+        let hover = match &self.hover {
+            Some(hover) if !self.suppressed => hover,
+            _ => return Ok(()),
+        };
+        if timer::time_since_start(ctx) < hover.since + self.delay || hover.text.is_empty() {
+            return Ok(());
+        }
+        let mut text = Text::new(
+            TextFragment::new(hover.text.as_str())
+                .scale(Scale::uniform(TEXT_SCALE))
+                .color(TEXT_COLOR),
+        );
+        text.set_bounds(Point2::new(MAX_WIDTH, f32::INFINITY), Align::Left);
+        let (w, h) = text.dimensions(ctx);
+        let anchor = Rect::new(hover.x, hover.y, 0., CURSOR_SIZE);
+        let rect = place_popup(
+            w as f32 + PADDING * 2.,
+            h as f32 + PADDING * 2.,
+            anchor,
+            Placement::Below,
+            bounds,
+        );
+        let panel = MeshBuilder::new()
+            .rectangle(DrawMode::fill(), rect, BACKGROUND)
+            .rectangle(DrawMode::stroke(1.), rect, TEXT_COLOR)
+            .build(ctx)?;
+        graphics::draw(ctx, &panel, DrawParam::default())?;
+        graphics::draw(
+            ctx,
+            &text,
+            DrawParam::default().dest(Point2::new(rect.x + PADDING, rect.y + PADDING)),
+        )
+    }
+}
@@ -0,0 +1,707 @@
+use crate::{add_to_indexmap, EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use async_std::task;
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::input::mouse;
+use ggez::{Context, GameResult};
+use indexmap::map::IndexMap;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+const WHEEL_STEP: usize = 3;
+
+#[derive(Clone, Default, Debug)]
+pub struct TreeNode {
+    pub label: String,
+    pub children: Vec<TreeNode>,
+    /// Children are requested from the loader when the node is expanded first time
+    pub lazy: bool,
+    pub expanded: bool,
+}
+
+impl TreeNode {
+    pub fn new<T: Into<String>>(label: T) -> Self {
+        Self {
+            label: label.into(),
+            ..Self::default()
+        }
+    }
+    pub fn with_children<T: Into<String>>(label: T, children: Vec<TreeNode>) -> Self {
+        Self {
+            label: label.into(),
+            children,
+            ..Self::default()
+        }
+    }
+    /// Node which children are loaded on demand
+    pub fn lazy<T: Into<String>>(label: T) -> Self {
+        Self {
+            label: label.into(),
+            lazy: true,
+            ..Self::default()
+        }
+    }
+    pub fn is_expandable(&self) -> bool {
+        self.lazy || !self.children.is_empty()
+    }
+}
+
+fn find_node<'a>(nodes: &'a [TreeNode], path: &[usize]) -> Option<&'a TreeNode> {
+    let (first, rest) = path.split_first()?;
+    let node = nodes.get(*first)?;
+    if rest.is_empty() {
+        Some(node)
+    } else {
+        find_node(&node.children, rest)
+    }
+}
+
+fn find_node_mut<'a>(nodes: &'a mut [TreeNode], path: &[usize]) -> Option<&'a mut TreeNode> {
+    let (first, rest) = path.split_first()?;
+    let node = nodes.get_mut(*first)?;
+    if rest.is_empty() {
+        Some(node)
+    } else {
+        find_node_mut(&mut node.children, rest)
+    }
+}
+
+/// Visible line of the tree
+#[derive(Clone, Default, Debug)]
+pub struct TreeRow {
+    pub label: String,
+    pub depth: usize,
+    pub expandable: bool,
+    pub expanded: bool,
+    /// Children are being loaded
+    pub loading: bool,
+    pub selected: bool,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct TreeViewState {
+    pub rows: Vec<TreeRow>,
+    /// Index of the first visible row
+    pub scroll: usize,
+    pub focused: bool,
+    pub rect: Rect,
+}
+
+pub trait TreeViewSkin: EventHandlerProxy + Default + Debug + Send {
+    fn set_state(&mut self, state: &TreeViewState);
+    fn is_hot_area(&self, x: f32, y: f32) -> bool;
+    fn row_at(&self, x: f32, y: f32) -> Option<usize>;
+    /// Point is on the expand/collapse arrow of the row
+    fn is_arrow_at(&self, row: usize, x: f32, y: f32) -> bool;
+    /// Number of fully visible rows
+    fn visible_count(&self) -> usize;
+}
+
+type Loader =
+    Arc<dyn Fn(Vec<usize>) -> Pin<Box<dyn Future<Output = Vec<TreeNode>> + Send>> + Send + Sync>;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TreeViewId(SrvId);
+
+impl From<TreeViewId> for SrvId {
+    fn from(v: TreeViewId) -> SrvId {
+        v.0
+    }
+}
+
+type SelectionChangedHandler = Box<dyn Fn(&mut dyn Widget, &[usize]) + Send + Sync>;
+
+enum TreeViewOp {
+    GetRoots,
+    SetRoots(Vec<TreeNode>),
+    SetExpanded(Vec<usize>, bool),
+    GetSelection,
+    Select(Option<Vec<usize>>),
+    OnSelectionChanged(SelectionChangedHandler),
+    RemoveOnSelectionChanged(usize),
+    ChildrenLoaded(usize, Vec<usize>, Vec<TreeNode>),
+}
+
+impl Debug for TreeViewOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TreeViewOp::GetRoots => write!(f, "GetRoots"),
+            TreeViewOp::SetRoots(roots) => write!(f, "SetRoots({:?})", roots),
+            TreeViewOp::SetExpanded(path, expanded) => {
+                write!(f, "SetExpanded({:?}, {:?})", path, expanded)
+            }
+            TreeViewOp::GetSelection => write!(f, "GetSelection"),
+            TreeViewOp::Select(path) => write!(f, "Select({:?})", path),
+            TreeViewOp::OnSelectionChanged(_) => write!(f, "OnSelectionChanged"),
+            TreeViewOp::RemoveOnSelectionChanged(handler_id) => {
+                write!(f, "RemoveOnSelectionChanged({:?})", handler_id)
+            }
+            TreeViewOp::ChildrenLoaded(generation, path, children) => {
+                write!(
+                    f,
+                    "ChildrenLoaded({:?}, {:?}, {:?})",
+                    generation, path, children
+                )
+            }
+        }
+    }
+}
+
+impl TreeViewId {
+    pub async fn get_roots(self) -> Vec<TreeNode> {
+        send_request(self.0, TreeViewOp::GetRoots).await.unwrap()
+    }
+    pub async fn set_roots(self, roots: Vec<TreeNode>) {
+        send_request(self.0, TreeViewOp::SetRoots(roots))
+            .await
+            .unwrap()
+    }
+    pub async fn expand(self, path: Vec<usize>) {
+        send_request(self.0, TreeViewOp::SetExpanded(path, true))
+            .await
+            .unwrap()
+    }
+    pub async fn collapse(self, path: Vec<usize>) {
+        send_request(self.0, TreeViewOp::SetExpanded(path, false))
+            .await
+            .unwrap()
+    }
+    /// Path of child indices from the root node to the selected one
+    pub async fn get_selection(self) -> Option<Vec<usize>> {
+        send_request(self.0, TreeViewOp::GetSelection)
+            .await
+            .unwrap()
+    }
+    /// Expands parents of the node to make it visible
+    pub async fn select(self, path: Option<Vec<usize>>) {
+        send_request(self.0, TreeViewOp::Select(path))
+            .await
+            .unwrap()
+    }
+    pub async fn on_selection_changed<F: Fn(&mut dyn Widget, &[usize]) + Send + Sync + 'static>(
+        self,
+        f: F,
+    ) -> usize {
+        send_request(self.0, TreeViewOp::OnSelectionChanged(Box::new(f)))
+            .await
+            .unwrap()
+    }
+    pub async fn remove_on_selection_changed(self, handler_id: usize) {
+        send_request(self.0, TreeViewOp::RemoveOnSelectionChanged(handler_id))
+            .await
+            .unwrap()
+    }
+    async fn children_loaded(self, generation: usize, path: Vec<usize>, children: Vec<TreeNode>) {
+        send_request(
+            self.0,
+            TreeViewOp::ChildrenLoaded(generation, path, children),
+        )
+        .await
+        .unwrap()
+    }
+}
+
+/// Hierarchy of expandable nodes with single selection
+pub struct TreeView<S: TreeViewSkin> {
+    state: TreeViewState,
+    skin: S,
+    reg: ServiceRegistration,
+    roots: Vec<TreeNode>,
+    selection: Option<Vec<usize>>,
+    /// Paths of the nodes shown in `state.rows`
+    row_paths: Vec<Vec<usize>>,
+    /// Nodes waiting for the loader
+    loading: Vec<Vec<usize>>,
+    /// Changed by `set_roots`, loads started for the previous roots are dropped
+    generation: usize,
+    loader: Option<Loader>,
+    on_selection_changed_handlers: IndexMap<usize, SelectionChangedHandler>,
+}
+
+impl<S: TreeViewSkin> TreeView<S> {
+    pub fn new() -> Self {
+        Self {
+            state: TreeViewState::default(),
+            skin: S::default(),
+            reg: register_service(),
+            roots: Vec::new(),
+            selection: None,
+            row_paths: Vec::new(),
+            loading: Vec::new(),
+            generation: 0,
+            loader: None,
+            on_selection_changed_handlers: IndexMap::new(),
+        }
+    }
+    pub fn id(&self) -> TreeViewId {
+        TreeViewId(self.reg.id())
+    }
+    /// Drops the selection
+    pub fn set_roots(&mut self, roots: Vec<TreeNode>) {
+        self.roots = roots;
+        self.selection = None;
+        self.loading.clear();
+        self.generation = self.generation.wrapping_add(1);
+        self.update_rows();
+    }
+    pub fn get_roots(&self) -> &[TreeNode] {
+        &self.roots
+    }
+    pub fn get_node(&self, path: &[usize]) -> Option<&TreeNode> {
+        find_node(&self.roots, path)
+    }
+    /// Async function returning children of the lazy node with given path
+    pub fn set_loader<F, R>(&mut self, loader: F)
+    where
+        F: Fn(Vec<usize>) -> R + Send + Sync + 'static,
+        R: Future<Output = Vec<TreeNode>> + Send + 'static,
+    {
+        self.loader = Some(Arc::new(move |path| Box::pin(loader(path))))
+    }
+    pub fn set_expanded(&mut self, path: &[usize], expanded: bool) {
+        let node = match find_node_mut(&mut self.roots, path) {
+            Some(node) if node.is_expandable() => node,
+            _ => return,
+        };
+        node.expanded = expanded;
+        if expanded && node.lazy {
+            self.load_children(path.to_vec());
+        }
+        self.update_rows();
+    }
+    pub fn get_selection(&self) -> Option<&[usize]> {
+        self.selection.as_deref()
+    }
+    pub fn select(&mut self, path: Option<Vec<usize>>) {
+        let path = path.filter(|p| find_node(&self.roots, p).is_some());
+        if let Some(path) = &path {
+            for depth in 1..path.len() {
+                if let Some(node) = find_node_mut(&mut self.roots, &path[..depth]) {
+                    node.expanded = true
+                }
+            }
+        }
+        self.selection = path;
+        self.update_rows();
+        self.ensure_selection_visible();
+    }
+    pub fn on_selection_changed_box(&mut self, handler: SelectionChangedHandler) -> usize {
+        add_to_indexmap(&mut self.on_selection_changed_handlers, handler)
+    }
+    pub fn on_selection_changed<F: Fn(&mut dyn Widget, &[usize]) + Send + Sync + 'static>(
+        &mut self,
+        f: F,
+    ) -> usize {
+        self.on_selection_changed_box(Box::new(f))
+    }
+    pub fn remove_on_selection_changed(&mut self, handler_id: usize) {
+        self.on_selection_changed_handlers.remove(&handler_id);
+    }
+    fn load_children(&mut self, path: Vec<usize>) {
+        if self.loading.contains(&path) {
+            return;
+        }
+        if let Some(loader) = self.loader.clone() {
+            let id = self.id();
+            let generation = self.generation;
+            self.loading.push(path.clone());
+            task::spawn(async move {
+                let children = loader(path.clone()).await;
+                id.children_loaded(generation, path, children).await
+            });
+        }
+    }
+    /// Ignores loads which were started before the roots were replaced
+    fn children_loaded(&mut self, generation: usize, path: Vec<usize>, children: Vec<TreeNode>) {
+        if generation != self.generation {
+            return;
+        }
+        self.loading.retain(|p| *p != path);
+        if let Some(node) = find_node_mut(&mut self.roots, &path) {
+            node.children = children;
+            node.lazy = false;
+        }
+        self.update_rows();
+    }
+    fn update_rows(&mut self) {
+        fn add_rows(
+            nodes: &[TreeNode],
+            path: &mut Vec<usize>,
+            view: (&Option<Vec<usize>>, &[Vec<usize>]),
+            rows: &mut Vec<TreeRow>,
+            paths: &mut Vec<Vec<usize>>,
+        ) {
+            let (selection, loading) = view;
+            for (index, node) in nodes.iter().enumerate() {
+                path.push(index);
+                rows.push(TreeRow {
+                    label: node.label.clone(),
+                    depth: path.len() - 1,
+                    expandable: node.is_expandable(),
+                    expanded: node.expanded,
+                    loading: loading.contains(path),
+                    selected: selection.as_ref() == Some(path),
+                });
+                paths.push(path.clone());
+                if node.expanded {
+                    add_rows(&node.children, path, view, rows, paths);
+                }
+                path.pop();
+            }
+        }
+        let mut rows = Vec::new();
+        let mut paths = Vec::new();
+        add_rows(
+            &self.roots,
+            &mut Vec::new(),
+            (&self.selection, &self.loading),
+            &mut rows,
+            &mut paths,
+        );
+        self.state.rows = rows;
+        self.row_paths = paths;
+        let max_scroll = self.state.rows.len().saturating_sub(1);
+        self.state.scroll = self.state.scroll.min(max_scroll);
+    }
+    fn selected_row(&self) -> Option<usize> {
+        let selection = self.selection.as_ref()?;
+        self.row_paths.iter().position(|p| p == selection)
+    }
+    fn ensure_selection_visible(&mut self) {
+        if let Some(row) = self.selected_row() {
+            self.skin.set_state(&self.state);
+            let visible = self.skin.visible_count().max(1);
+            if row < self.state.scroll {
+                self.state.scroll = row
+            } else if row >= self.state.scroll + visible {
+                self.state.scroll = row + 1 - visible
+            }
+        }
+    }
+}
+
+impl<S: TreeViewSkin> Default for TreeView<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: TreeViewSkin + 'static> TreeView<S> {
+    /// Selects node from user input and notifies handlers if it was changed
+    fn change_selection(&mut self, path: Vec<usize>) {
+        if self.selection.as_ref() == Some(&path) {
+            return;
+        }
+        self.selection = Some(path.clone());
+        self.update_rows();
+        self.ensure_selection_visible();
+        let handlers = std::mem::replace(&mut self.on_selection_changed_handlers, IndexMap::new());
+        for (_, handler) in &handlers {
+            handler(self, &path);
+        }
+        self.on_selection_changed_handlers = handlers;
+    }
+    fn select_row(&mut self, row: usize) {
+        if let Some(path) = self
+            .row_paths
+            .get(row.min(self.row_paths.len().saturating_sub(1)))
+        {
+            self.change_selection(path.clone())
+        }
+    }
+    /// Expands the collapsed node or selects the first child of the expanded one
+    fn expand_or_enter(&mut self, row: usize) {
+        let path = self.row_paths[row].clone();
+        let tree_row = &self.state.rows[row];
+        if tree_row.expandable && !tree_row.expanded {
+            self.set_expanded(&path, true)
+        } else if tree_row.expanded
+            && find_node(&self.roots, &path).is_some_and(|n| !n.children.is_empty())
+        {
+            self.select_row(row + 1)
+        }
+    }
+    /// Collapsing the node which contains selection moves selection to the node
+    fn toggle(&mut self, path: Vec<usize>) {
+        let expanded = match find_node(&self.roots, &path) {
+            Some(node) if node.is_expandable() => node.expanded,
+            _ => return,
+        };
+        self.set_expanded(&path, !expanded);
+        let hidden = self
+            .selection
+            .as_ref()
+            .is_some_and(|s| s.len() > path.len() && s.starts_with(&path));
+        if expanded && hidden {
+            self.change_selection(path)
+        }
+    }
+}
+
+impl<S: TreeViewSkin + 'static> Widget for TreeView<S> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.state.rect = rect;
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.state.rect)
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("rows", self.state.rows.len().to_string()),
+            ("selection", format!("{:?}", self.selection)),
+        ]
+    }
+}
+
+impl<S: TreeViewSkin + 'static> EventHandlerProxy for TreeView<S> {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            TreeViewOp::GetRoots => Some(Box::new(self.get_roots().to_vec())),
+            TreeViewOp::SetRoots(roots) => {
+                self.set_roots(roots);
+                Some(Box::new(()))
+            }
+            TreeViewOp::SetExpanded(path, expanded) => {
+                self.set_expanded(&path, expanded);
+                Some(Box::new(()))
+            }
+            TreeViewOp::GetSelection => Some(Box::new(self.selection.clone())),
+            TreeViewOp::Select(path) => {
+                self.select(path);
+                Some(Box::new(()))
+            }
+            TreeViewOp::OnSelectionChanged(handler) => {
+                Some(Box::new(self.on_selection_changed_box(handler)))
+            }
+            TreeViewOp::RemoveOnSelectionChanged(handler_id) => {
+                self.remove_on_selection_changed(handler_id);
+                Some(Box::new(()))
+            }
+            TreeViewOp::ChildrenLoaded(generation, path, children) => {
+                self.children_loaded(generation, path, children);
+                Some(Box::new(()))
+            }
+        });
+        self.skin.set_state(&self.state);
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.skin.set_state(&self.state);
+        self.skin.draw(ctx)
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        if button != MouseButton::Left {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        if !self.skin.is_hot_area(x, y) {
+            self.state.focused = false;
+            return false;
+        }
+        self.state.focused = true;
+        if let Some(row) = self.skin.row_at(x, y) {
+            let path = self.row_paths[row].clone();
+            if self.skin.is_arrow_at(row, x, y) {
+                self.toggle(path)
+            } else {
+                self.change_selection(path)
+            }
+        }
+        true
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) -> bool {
+        let pos = mouse::position(ctx);
+        self.skin.set_state(&self.state);
+        if !self.skin.is_hot_area(pos.x, pos.y) {
+            return false;
+        }
+        let max_scroll = self
+            .state
+            .rows
+            .len()
+            .saturating_sub(self.skin.visible_count());
+        if y > 0. {
+            self.state.scroll = self.state.scroll.saturating_sub(WHEEL_STEP);
+        } else if y < 0. {
+            self.state.scroll = (self.state.scroll + WHEEL_STEP).min(max_scroll);
+        }
+        true
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        _keymods: KeyMods,
+        _repeat: bool,
+    ) -> bool {
+        if !self.state.focused || self.state.rows.is_empty() {
+            return false;
+        }
+        self.skin.set_state(&self.state);
+        let page = self.skin.visible_count().max(1);
+        let row = self.selected_row();
+        match keycode {
+            KeyCode::Up => self.select_row(row.map_or(0, |n| n.saturating_sub(1))),
+            KeyCode::Down => self.select_row(row.map_or(0, |n| n + 1)),
+            KeyCode::PageUp => self.select_row(row.map_or(0, |n| n.saturating_sub(page))),
+            KeyCode::PageDown => self.select_row(row.map_or(0, |n| n + page)),
+            KeyCode::Home => self.select_row(0),
+            KeyCode::End => self.select_row(self.state.rows.len()),
+            KeyCode::Right => {
+                if let Some(row) = row {
+                    self.expand_or_enter(row)
+                }
+            }
+            KeyCode::Left => {
+                if let Some(row) = row {
+                    let mut path = self.row_paths[row].clone();
+                    if self.state.rows[row].expanded {
+                        self.set_expanded(&path, false)
+                    } else if path.len() > 1 {
+                        path.pop();
+                        self.change_selection(path)
+                    }
+                }
+            }
+            KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Space => {
+                if let Some(row) = row {
+                    self.toggle(self.row_paths[row].clone())
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+pub struct TreeViewBuilder<S: TreeViewSkin> {
+    tree_view: TreeView<S>,
+}
+
+impl<S: TreeViewSkin + 'static> Default for TreeViewBuilder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: TreeViewSkin + 'static> TreeViewBuilder<S> {
+    pub fn new() -> Self {
+        Self {
+            tree_view: TreeView::new(),
+        }
+    }
+    pub fn set_roots(mut self, roots: Vec<TreeNode>) -> Self {
+        self.tree_view.set_roots(roots);
+        self
+    }
+    pub fn set_loader<F, R>(mut self, loader: F) -> Self
+    where
+        F: Fn(Vec<usize>) -> R + Send + Sync + 'static,
+        R: Future<Output = Vec<TreeNode>> + Send + 'static,
+    {
+        self.tree_view.set_loader(loader);
+        self
+    }
+    pub fn on_selection_changed<F: Fn(&mut dyn Widget, &[usize]) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.tree_view.on_selection_changed(f);
+        self
+    }
+    pub fn build(self) -> TreeView<S> {
+        self.tree_view
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default, Debug)]
+    struct TestSkin;
+
+    impl EventHandlerProxy for TestSkin {}
+
+    impl TreeViewSkin for TestSkin {
+        fn set_state(&mut self, _state: &TreeViewState) {}
+        fn is_hot_area(&self, _x: f32, _y: f32) -> bool {
+            false
+        }
+        fn row_at(&self, _x: f32, _y: f32) -> Option<usize> {
+            None
+        }
+        fn is_arrow_at(&self, _row: usize, _x: f32, _y: f32) -> bool {
+            false
+        }
+        fn visible_count(&self) -> usize {
+            10
+        }
+    }
+
+    fn labels(tree_view: &TreeView<TestSkin>) -> Vec<&str> {
+        tree_view
+            .state
+            .rows
+            .iter()
+            .map(|r| r.label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn stale_load_is_dropped() {
+        let mut tree_view: TreeView<TestSkin> = TreeViewBuilder::new()
+            .set_roots(vec![TreeNode::lazy("a")])
+            .build();
+        let generation = tree_view.generation;
+        tree_view.loading.push(vec![0]);
+        tree_view.set_roots(vec![TreeNode::lazy("b")]);
+        tree_view.children_loaded(generation, vec![0], vec![TreeNode::new("a1")]);
+        assert!(tree_view.get_node(&[0]).unwrap().children.is_empty());
+        assert!(tree_view.get_node(&[0]).unwrap().lazy);
+        tree_view.loading.push(vec![0]);
+        tree_view.children_loaded(tree_view.generation, vec![0], vec![TreeNode::new("b1")]);
+        assert_eq!(tree_view.get_node(&[0, 0]).unwrap().label, "b1");
+        assert!(tree_view.loading.is_empty());
+    }
+
+    #[test]
+    fn right_key_enters_only_nodes_with_children() {
+        let mut tree_view: TreeView<TestSkin> = TreeViewBuilder::new()
+            .set_roots(vec![
+                TreeNode::with_children("a", vec![TreeNode::new("a1")]),
+                TreeNode::lazy("b"),
+                TreeNode::new("c"),
+            ])
+            .build();
+        tree_view.select(Some(vec![0]));
+        tree_view.expand_or_enter(0);
+        assert_eq!(labels(&tree_view), vec!["a", "a1", "b", "c"]);
+        assert_eq!(tree_view.get_selection(), Some(&[0][..]));
+        tree_view.expand_or_enter(0);
+        assert_eq!(tree_view.get_selection(), Some(&[0, 0][..]));
+        // lazy node loaded no children
+        tree_view.select(Some(vec![1]));
+        tree_view.set_expanded(&[1], true);
+        tree_view.children_loaded(tree_view.generation, vec![1], Vec::new());
+        tree_view.expand_or_enter(2);
+        assert_eq!(tree_view.get_selection(), Some(&[1][..]));
+    }
+}
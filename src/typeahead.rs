@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+/// Typed prefix is forgotten after this pause between key presses
+const RESET_DELAY: Duration = Duration::from_millis(1000);
+
+/// Type-to-select helper for item lists: collects typed chars into a prefix
+/// and finds the item starting with it, case insensitive.
+#[derive(Clone, Default, Debug)]
+pub struct TypeAhead {
+    prefix: String,
+    last_input: Option<Duration>,
+}
+
+impl TypeAhead {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn reset(&mut self) {
+        self.prefix.clear();
+        self.last_input = None;
+    }
+    /// Adds the char typed at time `now` and returns index of the matching item.
+    /// Repeating the same single char cycles through items starting with it.
+    pub fn input<S: AsRef<str>>(
+        &mut self,
+        now: Duration,
+        c: char,
+        items: &[S],
+        current: Option<usize>,
+    ) -> Option<usize> {
+        if let Some(last_input) = self.last_input {
+            if now.checked_sub(last_input).is_none_or(|d| d > RESET_DELAY) {
+                self.prefix.clear();
+            }
+        }
+        self.last_input = Some(now);
+        let c = c.to_lowercase().collect::<String>();
+        let cycling = self.prefix == c;
+        if !cycling {
+            self.prefix.push_str(&c);
+        }
+        if items.is_empty() {
+            return None;
+        }
+        // a fresh or repeated char looks from the next item, longer prefix may keep current one
+        let start = match current {
+            Some(current) if cycling || self.prefix == c => current + 1,
+            Some(current) => current,
+            None => 0,
+        };
+        (0..items.len())
+            .map(|n| (start + n) % items.len())
+            .find(|&n| items[n].as_ref().to_lowercase().starts_with(&self.prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: [&str; 4] = ["Apple", "banana", "Blueberry", "cherry"];
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn typed_chars_extend_prefix() {
+        let mut type_ahead = TypeAhead::new();
+        assert_eq!(type_ahead.input(ms(0), 'b', &ITEMS, None), Some(1));
+        assert_eq!(type_ahead.input(ms(100), 'L', &ITEMS, Some(1)), Some(2));
+        assert_eq!(type_ahead.input(ms(200), 'x', &ITEMS, Some(2)), None);
+    }
+
+    #[test]
+    fn longer_prefix_keeps_current_item() {
+        let mut type_ahead = TypeAhead::new();
+        assert_eq!(type_ahead.input(ms(0), 'b', &ITEMS, None), Some(1));
+        assert_eq!(type_ahead.input(ms(100), 'a', &ITEMS, Some(1)), Some(1));
+    }
+
+    #[test]
+    fn repeated_char_cycles_through_matches() {
+        let mut type_ahead = TypeAhead::new();
+        assert_eq!(type_ahead.input(ms(0), 'b', &ITEMS, None), Some(1));
+        assert_eq!(type_ahead.input(ms(100), 'b', &ITEMS, Some(1)), Some(2));
+        assert_eq!(type_ahead.input(ms(200), 'b', &ITEMS, Some(2)), Some(1));
+    }
+
+    #[test]
+    fn pause_resets_prefix() {
+        let mut type_ahead = TypeAhead::new();
+        assert_eq!(type_ahead.input(ms(0), 'b', &ITEMS, None), Some(1));
+        assert_eq!(type_ahead.input(ms(2000), 'c', &ITEMS, Some(1)), Some(3));
+        // clock going backwards resets too
+        assert_eq!(type_ahead.input(ms(1000), 'a', &ITEMS, Some(3)), Some(0));
+    }
+
+    #[test]
+    fn reset_and_empty_items() {
+        let mut type_ahead = TypeAhead::new();
+        let empty: [&str; 0] = [];
+        assert_eq!(type_ahead.input(ms(0), 'a', &empty, None), None);
+        type_ahead.reset();
+        assert_eq!(type_ahead.input(ms(100), 'c', &ITEMS, None), Some(3));
+    }
+}
@@ -0,0 +1,419 @@
+use crate::overlay::OverlayId;
+use crate::{EventHandlerProxy, Widget};
+use async_call::{register_service, send_request, serve_requests, ServiceRegistration, SrvId};
+use ggez::event::MouseButton;
+use ggez::graphics::Rect;
+use ggez::input::keyboard::{KeyCode, KeyMods};
+use ggez::input::mouse;
+use ggez::nalgebra::Point2;
+use ggez::{Context, GameResult};
+
+const DEFAULT_ROW_HEIGHT: f32 = 30.;
+const WHEEL_STEP: usize = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VirtualListId(SrvId);
+
+impl From<VirtualListId> for SrvId {
+    fn from(v: VirtualListId) -> SrvId {
+        v.0
+    }
+}
+
+#[derive(Debug)]
+enum VirtualListOp {
+    GetItemCount,
+    SetItemCount(usize),
+    GetScroll,
+    ScrollTo(usize),
+    Refresh,
+}
+
+impl VirtualListId {
+    pub async fn get_item_count(self) -> usize {
+        send_request(self.0, VirtualListOp::GetItemCount)
+            .await
+            .unwrap()
+    }
+    pub async fn set_item_count(self, count: usize) {
+        send_request(self.0, VirtualListOp::SetItemCount(count))
+            .await
+            .unwrap()
+    }
+    /// Index of the first visible item
+    pub async fn get_scroll(self) -> usize {
+        send_request(self.0, VirtualListOp::GetScroll)
+            .await
+            .unwrap()
+    }
+    /// Scrolls the item into view
+    pub async fn scroll_to(self, index: usize) {
+        send_request(self.0, VirtualListOp::ScrollTo(index))
+            .await
+            .unwrap()
+    }
+    /// Binds visible rows again after the items data was changed
+    pub async fn refresh(self) {
+        send_request(self.0, VirtualListOp::Refresh).await.unwrap()
+    }
+}
+
+struct Row<W> {
+    widget: W,
+    /// Item shown by the row, `None` for the row waiting in the pool
+    index: Option<usize>,
+}
+
+type CreateFn<W> = Box<dyn Fn() -> W + Send + Sync>;
+type BindFn<W> = Box<dyn Fn(&mut W, usize) + Send + Sync>;
+
+/// Vertical list of rows of equal height which keeps widgets only for the
+/// visible items. Row widgets are made by `create` and filled with the item
+/// data by `bind`; rows scrolled out of view are bound to new items instead
+/// of being dropped.
+pub struct VirtualList<W: Widget> {
+    rect: Rect,
+    reg: ServiceRegistration,
+    item_count: usize,
+    row_height: f32,
+    scroll: usize,
+    rows: Vec<Row<W>>,
+    create: CreateFn<W>,
+    bind: BindFn<W>,
+    overlay: Option<OverlayId>,
+}
+
+impl<W: Widget> VirtualList<W> {
+    pub fn new<C, B>(create: C, bind: B) -> Self
+    where
+        C: Fn() -> W + Send + Sync + 'static,
+        B: Fn(&mut W, usize) + Send + Sync + 'static,
+    {
+        Self {
+            rect: Rect::default(),
+            reg: register_service(),
+            item_count: 0,
+            row_height: DEFAULT_ROW_HEIGHT,
+            scroll: 0,
+            rows: Vec::new(),
+            create: Box::new(create),
+            bind: Box::new(bind),
+            overlay: None,
+        }
+    }
+    pub fn id(&self) -> VirtualListId {
+        VirtualListId(self.reg.id())
+    }
+    pub fn set_item_count(&mut self, count: usize) {
+        self.item_count = count;
+        self.update_rows();
+    }
+    pub fn get_item_count(&self) -> usize {
+        self.item_count
+    }
+    pub fn set_row_height(&mut self, row_height: f32) {
+        self.row_height = row_height.max(1.);
+        self.update_rows();
+    }
+    pub fn get_row_height(&self) -> f32 {
+        self.row_height
+    }
+    pub fn get_scroll(&self) -> usize {
+        self.scroll
+    }
+    pub fn scroll_to(&mut self, index: usize) {
+        let visible = self.visible_count().max(1);
+        if index < self.scroll {
+            self.scroll = index
+        } else if index >= self.scroll + visible {
+            self.scroll = index + 1 - visible
+        }
+        self.update_rows();
+    }
+    pub fn refresh(&mut self) {
+        let bind = &self.bind;
+        for row in &mut self.rows {
+            if let Some(index) = row.index {
+                bind(&mut row.widget, index)
+            }
+        }
+    }
+    /// Number of rows fitting entirely into the rect
+    fn visible_count(&self) -> usize {
+        (self.rect.h / self.row_height).floor().max(0.) as usize
+    }
+    /// Number of rows at least partially inside the rect
+    fn shown_count(&self) -> usize {
+        (self.rect.h / self.row_height).ceil().max(0.) as usize
+    }
+    fn scroll_by(&mut self, delta: isize) {
+        let scroll = self.scroll as isize + delta;
+        self.scroll = scroll.max(0) as usize;
+        self.update_rows();
+    }
+    /// Binds rows to the visible items reusing rows which went out of view
+    fn update_rows(&mut self) {
+        let shown = self.shown_count();
+        self.scroll = self
+            .scroll
+            .min(self.item_count.saturating_sub(self.visible_count()));
+        let range = self.scroll..(self.scroll + shown).min(self.item_count);
+        for row in &mut self.rows {
+            if row.index.is_some_and(|n| !range.contains(&n)) {
+                row.index = None
+            }
+        }
+        for index in range {
+            let pos = match self.rows.iter().position(|r| r.index == Some(index)) {
+                Some(pos) => pos,
+                None => {
+                    let pos = match self.rows.iter().position(|r| r.index.is_none()) {
+                        Some(pos) => pos,
+                        None => {
+                            let mut widget = (self.create)();
+                            if let Some(overlay) = self.overlay {
+                                widget.attach_overlay(overlay)
+                            }
+                            self.rows.push(Row {
+                                widget,
+                                index: None,
+                            });
+                            self.rows.len() - 1
+                        }
+                    };
+                    (self.bind)(&mut self.rows[pos].widget, index);
+                    self.rows[pos].index = Some(index);
+                    pos
+                }
+            };
+            // the bottom row is clipped to the list's rect
+            let y = self.rect.y + (index - self.scroll) as f32 * self.row_height;
+            let h = self.row_height.min(self.rect.y + self.rect.h - y);
+            self.rows[pos]
+                .widget
+                .set_rect(Rect::new(self.rect.x, y, self.rect.w, h));
+        }
+        // pool doesn't grow beyond the rows needed for the current rect
+        if self.rows.len() > shown {
+            self.rows.retain(|r| r.index.is_some())
+        }
+    }
+    fn shown_rows_mut(&mut self) -> impl Iterator<Item = &mut W> {
+        self.rows
+            .iter_mut()
+            .filter(|r| r.index.is_some())
+            .map(|r| &mut r.widget)
+    }
+}
+
+impl<W: Widget + 'static> Widget for VirtualList<W> {
+    fn srv_id(&self) -> SrvId {
+        self.reg.id()
+    }
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.update_rows();
+    }
+    fn get_rect(&self) -> Option<Rect> {
+        Some(self.rect)
+    }
+    fn children(&self) -> Vec<&dyn Widget> {
+        self.rows
+            .iter()
+            .filter(|r| r.index.is_some())
+            .map(|r| &r.widget as &dyn Widget)
+            .collect()
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn Widget> {
+        self.shown_rows_mut()
+            .map(|w| w as &mut dyn Widget)
+            .collect()
+    }
+    fn properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("items", self.item_count.to_string()),
+            ("scroll", self.scroll.to_string()),
+            ("rows", self.rows.len().to_string()),
+        ]
+    }
+    fn attach_overlay(&mut self, overlay: OverlayId) {
+        self.overlay = Some(overlay);
+        for row in &mut self.rows {
+            row.widget.attach_overlay(overlay)
+        }
+    }
+}
+
+impl<W: Widget + 'static> EventHandlerProxy for VirtualList<W> {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        serve_requests(self.reg.id(), |req| match req {
+            VirtualListOp::GetItemCount => Some(Box::new(self.get_item_count())),
+            VirtualListOp::SetItemCount(count) => {
+                self.set_item_count(count);
+                Some(Box::new(()))
+            }
+            VirtualListOp::GetScroll => Some(Box::new(self.get_scroll())),
+            VirtualListOp::ScrollTo(index) => {
+                self.scroll_to(index);
+                Some(Box::new(()))
+            }
+            VirtualListOp::Refresh => {
+                self.refresh();
+                Some(Box::new(()))
+            }
+        });
+        for w in self.shown_rows_mut() {
+            w.update(ctx)?
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        for w in self.shown_rows_mut() {
+            w.draw(ctx)?
+        }
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        for w in self.shown_rows_mut() {
+            consumed |= w.mouse_button_down_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> bool {
+        let mut consumed = false;
+        for w in self.shown_rows_mut() {
+            consumed |= w.mouse_button_up_event(ctx, button, x, y)
+        }
+        consumed
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) -> bool {
+        let mut consumed = false;
+        for w in self.shown_rows_mut() {
+            consumed |= w.mouse_motion_event(ctx, x, y, dx, dy)
+        }
+        consumed
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) -> bool {
+        let mut consumed = false;
+        for w in self.shown_rows_mut() {
+            consumed |= w.mouse_wheel_event(ctx, x, y)
+        }
+        let pos = mouse::position(ctx);
+        if consumed || !self.rect.contains(Point2::new(pos.x, pos.y)) {
+            return consumed;
+        }
+        if y > 0. {
+            self.scroll_by(-(WHEEL_STEP as isize))
+        } else if y < 0. {
+            self.scroll_by(WHEEL_STEP as isize)
+        }
+        true
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        repeat: bool,
+    ) -> bool {
+        let mut consumed = false;
+        for w in self.shown_rows_mut() {
+            consumed |= w.key_down_event(ctx, keycode, keymods, repeat)
+        }
+        consumed
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, keymods: KeyMods) -> bool {
+        let mut consumed = false;
+        for w in self.shown_rows_mut() {
+            consumed |= w.key_up_event(ctx, keycode, keymods)
+        }
+        consumed
+    }
+
+    fn text_input_event(&mut self, ctx: &mut Context, character: char) -> bool {
+        let mut consumed = false;
+        for w in self.shown_rows_mut() {
+            consumed |= w.text_input_event(ctx, character)
+        }
+        consumed
+    }
+}
+
+pub struct VirtualListBuilder<W: Widget> {
+    virtual_list: VirtualList<W>,
+}
+
+impl<W: Widget + 'static> VirtualListBuilder<W> {
+    /// `create` makes an empty row widget, `bind` shows the item with given index in it
+    pub fn new<C, B>(create: C, bind: B) -> Self
+    where
+        C: Fn() -> W + Send + Sync + 'static,
+        B: Fn(&mut W, usize) + Send + Sync + 'static,
+    {
+        Self {
+            virtual_list: VirtualList::new(create, bind),
+        }
+    }
+    pub fn set_item_count(mut self, count: usize) -> Self {
+        self.virtual_list.set_item_count(count);
+        self
+    }
+    pub fn set_row_height(mut self, row_height: f32) -> Self {
+        self.virtual_list.set_row_height(row_height);
+        self
+    }
+    pub fn build(self) -> VirtualList<W> {
+        self.virtual_list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stack::Stack;
+
+    fn row_rects(list: &VirtualList<Stack>) -> Vec<Rect> {
+        list.children()
+            .iter()
+            .map(|w| w.get_rect().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn partial_bottom_row_is_clipped() {
+        let mut list = VirtualListBuilder::new(Stack::new, |_, _| {})
+            .set_item_count(10)
+            .set_row_height(30.)
+            .build();
+        list.set_rect(Rect::new(0., 0., 100., 75.));
+        let rects = row_rects(&list);
+        assert_eq!(rects.len(), 3);
+        assert_eq!(rects[2], Rect::new(0., 60., 100., 15.));
+        // scrolled to the end the last item is fully visible
+        list.scroll_to(9);
+        assert_eq!(list.get_scroll(), 8);
+        let rects = row_rects(&list);
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects[1], Rect::new(0., 30., 100., 30.));
+    }
+}